@@ -9,6 +9,15 @@ import {
 } from "@solana/web3.js"
 import BN from "bn.js"
 
+/**
+ * @deprecated This client hand-packs the original unversioned instruction
+ * format with the original account lists. The program now rejects that data
+ * with `UnsupportedInstructionVersion`: instruction data is
+ * `INSTRUCTION_VERSION` (0x80) followed by the Borsh encoding of
+ * `ColabioInstruction`, and each variant documents its accounts in
+ * `src/lib.rs`. Constructing the client throws so that callers fail before
+ * sending transactions the program would reject.
+ */
 export class ColabioClient {
   connection: Connection
   programId: PublicKey
@@ -16,6 +25,9 @@ export class ColabioClient {
   constructor(connection: Connection, programId: PublicKey) {
     this.connection = connection
     this.programId = programId
+    throw new Error(
+      "ColabioClient is deprecated: it builds the unversioned instruction format the program no longer accepts",
+    )
   }
 
   async initializeProject(
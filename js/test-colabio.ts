@@ -1,3 +1,4 @@
+// Exercises the deprecated ColabioClient; see the note on the class before running it
 import { Connection, Keypair, PublicKey, clusterApiUrl } from "@solana/web3.js"
 import { ColabioClient } from "./colabio-client"
 
@@ -1,17 +1,195 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use num_derive::FromPrimitive;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    bpf_loader_upgradeable,
+    decode_error::DecodeError,
     entrypoint,
-    entrypoint::ProgramResult,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
     msg,
-    program_error::ProgramError,
+    program_error::{PrintProgramError, ProgramError},
     pubkey::Pubkey,
     rent::Rent,
     sysvar::Sysvar,
     program::{invoke, invoke_signed},
     system_instruction,
 };
-use std::convert::TryInto;
+use spl_token_2022::{
+    check_spl_token_program_account,
+    extension::{metadata_pointer, BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    instruction::AuthorityType,
+    state::{Account as TokenAccount, Mint},
+};
+use spl_token_metadata_interface::state::{Field, TokenMetadata};
+use thiserror::Error;
+
+/// Seed prefix for a project's escrow vault PDA.
+///
+/// The vault for a project lives at
+/// `find_program_address(&[VAULT_SEED, project.as_ref()], program_id)`. It is a
+/// system-owned account that only this program can sign for, and it holds every
+/// lamport contributed to the project until it is released or refunded.
+pub const VAULT_SEED: &[u8] = b"vault";
+
+/// Derives the escrow vault address and canonical bump for a project
+pub fn find_vault_address(program_id: &Pubkey, project: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, project.as_ref()], program_id)
+}
+
+/// Seed prefix for a token project's escrow token account PDA.
+///
+/// Projects that accept an SPL Token or Token-2022 mint escrow it in a token
+/// account at `find_program_address(&[VAULT_TOKEN_SEED, project.as_ref()], program_id)`,
+/// whose token authority is the project's vault PDA.
+pub const VAULT_TOKEN_SEED: &[u8] = b"vault_token";
+
+/// Derives the escrow token account address and canonical bump for a project
+pub fn find_vault_token_address(program_id: &Pubkey, project: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_TOKEN_SEED, project.as_ref()], program_id)
+}
+
+/// Seed prefix for a project's creator bond PDA.
+///
+/// The bond lives at `find_program_address(&[BOND_SEED, project.as_ref()], program_id)`:
+/// a system account holding lamports for SOL projects, or a token account
+/// whose token authority is the project's vault PDA for token projects.
+pub const BOND_SEED: &[u8] = b"bond";
+
+/// Derives the creator bond address and canonical bump for a project
+pub fn find_bond_address(program_id: &Pubkey, project: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BOND_SEED, project.as_ref()], program_id)
+}
+
+/// Seed prefix for contribution receipt mint PDAs.
+///
+/// A receipt is a supply-1 Token-2022 mint at
+/// `find_program_address(&[RECEIPT_SEED, contribution.as_ref()], program_id)`
+/// that carries its own token metadata.
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+
+/// Name written into every receipt's token metadata
+pub const RECEIPT_NAME: &str = "Colabio Contribution Receipt";
+
+/// Symbol written into every receipt's token metadata
+pub const RECEIPT_SYMBOL: &str = "CLBR";
+
+/// Derives the receipt mint address and canonical bump for a contribution
+pub fn find_receipt_mint_address(program_id: &Pubkey, contribution: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECEIPT_SEED, contribution.as_ref()], program_id)
+}
+
+/// Seed prefix for vote record PDAs.
+///
+/// Each wallet gets exactly one vote record per project, at
+/// `find_program_address(&[VOTE_SEED, project.as_ref(), voter.as_ref()], program_id)`.
+pub const VOTE_SEED: &[u8] = b"vote";
+
+/// Derives the vote record address and canonical bump for a voter on a project
+pub fn find_vote_address(program_id: &Pubkey, project: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOTE_SEED, project.as_ref(), voter.as_ref()], program_id)
+}
+
+/// Seed prefix for milestone validation record PDAs.
+///
+/// A validator gets one record per validation round of a project milestone, at
+/// `find_program_address(&[VALIDATION_SEED, project.as_ref(), &[milestone_index], &round.to_le_bytes(), validator.as_ref()], program_id)`.
+pub const VALIDATION_SEED: &[u8] = b"validation";
+
+/// Derives the validation record address and canonical bump for a validator
+/// on a milestone's validation round
+pub fn find_validation_address(
+    program_id: &Pubkey,
+    project: &Pubkey,
+    milestone_index: u8,
+    round: u32,
+    validator: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VALIDATION_SEED, project.as_ref(), &[milestone_index], &round.to_le_bytes(), validator.as_ref()],
+        program_id,
+    )
+}
+
+/// Seed prefix for validator stake PDAs.
+///
+/// Each validator has one stake account holding its bonded SOL, at
+/// `find_program_address(&[VALIDATOR_SEED, validator.as_ref()], program_id)`.
+pub const VALIDATOR_SEED: &[u8] = b"validator";
+
+/// Derives the validator stake address and canonical bump for a validator
+pub fn find_validator_stake_address(program_id: &Pubkey, validator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VALIDATOR_SEED, validator.as_ref()], program_id)
+}
+
+/// Seed prefix for contributor milestone vote record PDAs.
+///
+/// Votes are keyed on the contribution rather than the wallet, so each
+/// contribution's weight counts once per validation round of a milestone even
+/// if its receipt changes hands:
+/// `find_program_address(&[MILESTONE_VOTE_SEED, contribution.as_ref(), &[milestone_index], &round.to_le_bytes()], program_id)`.
+pub const MILESTONE_VOTE_SEED: &[u8] = b"milestone_vote";
+
+/// Derives the milestone vote record address and canonical bump for a
+/// contribution on a milestone's validation round
+pub fn find_milestone_vote_address(
+    program_id: &Pubkey,
+    contribution: &Pubkey,
+    milestone_index: u8,
+    round: u32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MILESTONE_VOTE_SEED, contribution.as_ref(), &[milestone_index], &round.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Seed prefix for milestone dispute PDAs.
+///
+/// A milestone can be disputed once per validation round, at
+/// `find_program_address(&[DISPUTE_SEED, project.as_ref(), &[milestone_index], &round.to_le_bytes()], program_id)`.
+pub const DISPUTE_SEED: &[u8] = b"dispute";
+
+/// Derives the dispute address and canonical bump for a milestone's validation round
+pub fn find_dispute_address(
+    program_id: &Pubkey,
+    project: &Pubkey,
+    milestone_index: u8,
+    round: u32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DISPUTE_SEED, project.as_ref(), &[milestone_index], &round.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Seed prefix for dispute vote record PDAs.
+///
+/// Like milestone votes, dispute votes are keyed on the contribution:
+/// `find_program_address(&[DISPUTE_VOTE_SEED, dispute.as_ref(), contribution.as_ref()], program_id)`.
+pub const DISPUTE_VOTE_SEED: &[u8] = b"dispute_vote";
+
+/// Derives the dispute vote record address and canonical bump for a contribution
+pub fn find_dispute_vote_address(
+    program_id: &Pubkey,
+    dispute: &Pubkey,
+    contribution: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DISPUTE_VOTE_SEED, dispute.as_ref(), contribution.as_ref()],
+        program_id,
+    )
+}
+
+/// Seed for the program's singleton Config PDA, `[CONFIG_SEED]`
+pub const CONFIG_SEED: &[u8] = b"config";
+
+/// Derives the Config address and canonical bump
+pub fn find_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], program_id)
+}
+
+/// Denominator for all basis-point settings in `Config`
+pub const BPS_DENOMINATOR: u64 = 10_000;
 
 // Declare and export the program's entrypoint
 entrypoint!(process_instruction);
@@ -25,21 +203,15 @@ pub fn process_instruction(
     // Unpack instruction data
     let instruction = ColabioInstruction::unpack(instruction_data)?;
     
-    match instruction {
-        ColabioInstruction::InitializeProject {
-            title,
-            description,
-            goal_amount,
-            duration,
-            milestones,
-        } => {
-            process_initialize_project(program_id, accounts, title, description, goal_amount, duration, milestones)
+    let result = match instruction {
+        ColabioInstruction::InitializeProject { args } => {
+            process_initialize_project(program_id, accounts, args)
         }
-        ColabioInstruction::Contribute { amount } => {
-            process_contribute(program_id, accounts, amount)
+        ColabioInstruction::Contribute { amount, mint_receipt } => {
+            process_contribute(program_id, accounts, amount, mint_receipt)
         }
-        ColabioInstruction::ValidateMilestone { milestone_index } => {
-            process_validate_milestone(program_id, accounts, milestone_index)
+        ColabioInstruction::ValidateMilestone { milestone_index, evidence_hash, approve } => {
+            process_validate_milestone(program_id, accounts, milestone_index, evidence_hash, approve)
         }
         ColabioInstruction::ReleaseFunds { milestone_index } => {
             process_release_funds(program_id, accounts, milestone_index)
@@ -50,465 +222,4269 @@ pub fn process_instruction(
         ColabioInstruction::Vote { approve } => {
             process_vote(program_id, accounts, approve)
         }
+        ColabioInstruction::ClaimRefund {} => {
+            process_claim_refund(program_id, accounts)
+        }
+        ColabioInstruction::AddMilestone { milestone } => {
+            process_add_milestone(program_id, accounts, milestone)
+        }
+        ColabioInstruction::InitializeConfig { params } => {
+            process_initialize_config(program_id, accounts, params)
+        }
+        ColabioInstruction::UpdateConfig { new_admin, params } => {
+            process_update_config(program_id, accounts, new_admin, params)
+        }
+        ColabioInstruction::FinalizeFunding {} => {
+            process_finalize_funding(program_id, accounts)
+        }
+        ColabioInstruction::VoteMilestone { milestone_index, approve } => {
+            process_vote_milestone(program_id, accounts, milestone_index, approve)
+        }
+        ColabioInstruction::TallyVotes {} => {
+            process_tally_votes(program_id, accounts)
+        }
+        ColabioInstruction::RegisterValidator { amount } => {
+            process_register_validator(program_id, accounts, amount)
+        }
+        ColabioInstruction::DeregisterValidator {} => {
+            process_deregister_validator(program_id, accounts)
+        }
+        ColabioInstruction::WithdrawValidatorStake {} => {
+            process_withdraw_validator_stake(program_id, accounts)
+        }
+        ColabioInstruction::OverturnMilestone { milestone_index } => {
+            process_overturn_milestone(program_id, accounts, milestone_index)
+        }
+        ColabioInstruction::SlashValidator {} => {
+            process_slash_validator(program_id, accounts)
+        }
+        ColabioInstruction::OpenDispute { milestone_index, evidence_hash } => {
+            process_open_dispute(program_id, accounts, milestone_index, evidence_hash)
+        }
+        ColabioInstruction::VoteDispute { uphold } => {
+            process_vote_dispute(program_id, accounts, uphold)
+        }
+        ColabioInstruction::ResolveDispute {} => {
+            process_resolve_dispute(program_id, accounts, None)
+        }
+        ColabioInstruction::ArbitrateDispute { uphold } => {
+            process_resolve_dispute(program_id, accounts, Some(uphold))
+        }
+        ColabioInstruction::SubmitMilestoneEvidence { milestone_index, evidence } => {
+            process_submit_milestone_evidence(program_id, accounts, milestone_index, evidence)
+        }
+        ColabioInstruction::MarkDefault { milestone_index } => {
+            process_mark_default(program_id, accounts, milestone_index)
+        }
+        ColabioInstruction::ReclaimBond {} => {
+            process_reclaim_bond(program_id, accounts)
+        }
+    };
+    
+    // Log a readable message for program-specific errors
+    if let Err(error) = &result {
+        error.print::<ColabioError>();
     }
+    result
 }
 
-// Instructions supported by the program
+/// Version byte that prefixes every instruction's data.
+///
+/// The legacy hand-packed format started with an instruction tag in `0..=6`,
+/// so versions start at `0x80` to keep old clients from being misparsed.
+pub const INSTRUCTION_VERSION: u8 = 0x80;
+
+/// Instructions supported by the program
+/// 
+/// Instruction data is `INSTRUCTION_VERSION` followed by the Borsh encoding of
+/// this enum. Borsh tags variants by position, so new instructions must only
+/// ever be appended.
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub enum ColabioInstruction {
     /// Initialize a new project
     /// 
     /// Accounts expected:
-    /// 0. `[signer]` Project creator
+    /// 0. `[signer, writable]` Project creator
     /// 1. `[writable]` Project account, must be uninitialized
-    /// 2. `[]` Rent sysvar
-    /// 3. `[]` System program
+    /// 2. `[writable]` Project vault PDA, `[VAULT_SEED, project]`
+    /// 3. `[]` Config PDA
+    /// 4. `[]` Rent sysvar
+    /// 5. `[]` System program
+    /// 
+    /// Token projects (`args.mint` set) also pass:
+    /// 6. `[]` Mint
+    /// 7. `[writable]` Escrow token account PDA, `[VAULT_TOKEN_SEED, project]`
+    /// 8. `[]` Token program, SPL Token or Token-2022
+    /// 
+    /// Every project then passes:
+    /// - `[writable]` Creator bond PDA, `[BOND_SEED, project]`
+    /// 
+    /// and token projects finally pass:
+    /// - `[writable]` Creator's token account funding the bond
+    /// 
+    /// The creator deposits a bond of the Config `creator_bond_bps` share of
+    /// the goal, in the project's currency.
     InitializeProject {
-        title: String,
-        description: String,
-        goal_amount: u64,
-        duration: u64,
-        milestones: Vec<Milestone>,
+        args: ProjectArgs,
     },
     
     /// Contribute funds to a project
     /// 
+    /// For mints that charge transfer fees, the contribution is recorded at
+    /// the net amount the escrow actually received.
+    /// 
+    /// With `mint_receipt`, a supply-1 receipt token is minted to the
+    /// contributor. Whoever holds it, rather than the contributor's key,
+    /// controls the contribution's refund and governance rights.
+    /// 
     /// Accounts expected:
-    /// 0. `[signer]` Contributor
+    /// 0. `[signer, writable]` Contributor
     /// 1. `[writable]` Project account
-    /// 2. `[writable]` Contribution account, must be uninitialized
-    /// 3. `[]` System program
+    /// 2. `[writable]` Project vault PDA
+    /// 3. `[writable]` Contribution account, must be uninitialized
+    /// 4. `[]` System program
+    /// 
+    /// Token projects also pass:
+    /// - `[]` Mint
+    /// - `[writable]` Escrow token account PDA
+    /// - `[]` Token program
+    /// - `[writable]` Contributor's token account to pay from
+    /// 
+    /// With `mint_receipt`, these follow:
+    /// - `[writable]` Receipt mint PDA, `[RECEIPT_SEED, contribution]`
+    /// - `[writable]` Contributor's associated token account for the receipt
+    /// - `[]` Token-2022 program
+    /// - `[]` Associated token account program
     Contribute {
         amount: u64,
+        mint_receipt: bool,
     },
     
     /// Validate a milestone
     /// 
+    /// Only registered validators with at least the Config minimum stake may
+    /// validate, and only once the creator has submitted evidence;
+    /// `evidence_hash` must match the milestone's latest evidence entry. Each
+    /// validator may validate a milestone once per validation round, and the
+    /// project creator may not validate their own milestones.
+    /// A milestone becomes `Validated` once it has the Config validation count
+    /// and its funder vote passes, and `Rejected` once it has the Config
+    /// rejection count. A rejected milestone with no resubmissions left
+    /// cancels the project so contributors can claim refunds.
+    /// 
+    /// Milestones whose prerequisites have not all been released cannot be
+    /// validated.
+    /// 
     /// Accounts expected:
-    /// 0. `[signer]` Validator
+    /// 0. `[signer, writable]` Validator
     /// 1. `[writable]` Project account
-    /// 2. `[writable]` Validation account
-    /// 3. `[]` System program
+    /// 2. `[]` Config PDA
+    /// 3. `[writable]` Validation record PDA, `[VALIDATION_SEED, project, milestone_index, round, validator]`
+    /// 4. `[]` System program
+    /// 5. `[]` Validator stake PDA, `[VALIDATOR_SEED, validator]`
     ValidateMilestone {
         milestone_index: u8,
+        /// Hash of the latest evidence the validator reviewed
+        evidence_hash: [u8; 32],
+        /// Whether the validator approves or rejects the milestone
+        approve: bool,
     },
     
-    /// Release funds for a completed milestone
+    /// Release funds for a validated milestone
+    /// 
+    /// Each milestone can be released exactly once, after its prerequisites
+    /// and outside its challenge window. The Config fee is paid to the fee
+    /// recipient and the rest goes to the creator.
     /// 
     /// Accounts expected:
-    /// 0. `[signer]` Project creator
+    /// 0. `[signer, writable]` Project creator
     /// 1. `[writable]` Project account
-    /// 2. `[]` System program
+    /// 2. `[writable]` Project vault PDA
+    /// 3. `[]` Config PDA
+    /// 4. `[writable]` Fee recipient from Config
+    /// 5. `[]` System program
+    /// 
+    /// Token projects also pass:
+    /// 6. `[]` Mint
+    /// 7. `[writable]` Escrow token account PDA
+    /// 8. `[]` Token program
+    /// 9. `[writable]` Creator's token account
+    /// 10. `[writable]` Fee recipient's token account
     ReleaseFunds {
         milestone_index: u8,
     },
     
-    /// Cancel a project and refund contributors
+    /// Cancel a project so contributors can claim refunds
+    /// 
+    /// Contributors recover their share of the escrow with `ClaimRefund`.
     /// 
     /// Accounts expected:
     /// 0. `[signer]` Project creator
     /// 1. `[writable]` Project account
-    /// 2. `[]` System program
     CancelProject {},
     
     /// Vote on a project
     /// 
+    /// Each wallet may vote once per project; the vote record PDA is created
+    /// on the first vote and its existence blocks any further votes.
+    /// 
+    /// Votes are only accepted between the project's `voting_start` and
+    /// `voting_end`; the outcome is decided afterwards by `TallyVotes`.
+    /// 
     /// Accounts expected:
-    /// 0. `[signer]` Voter
+    /// 0. `[signer, writable]` Voter
     /// 1. `[writable]` Project account
-    /// 2. `[writable]` Vote account
+    /// 2. `[writable]` Vote record PDA, `[VOTE_SEED, project, voter]`
     /// 3. `[]` System program
     Vote {
         approve: bool,
     },
+    
+    /// Claim a refund for a contribution to a cancelled, failed or defaulted
+    /// project
+    /// 
+    /// A project is refundable once it is `Cancelled`, `Failed` or
+    /// `Defaulted`, or once it is `Completed` with escrow left over from its
+    /// remainder or from over-funding. The contributor receives their pro-rata
+    /// share of the escrow that has not been released to the creator, plus a
+    /// pro-rata share of the creator bond if the project defaulted or was
+    /// cancelled after activation.
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Contributor, or the receipt holder for
+    ///    contributions with a receipt
+    /// 1. `[writable]` Project account
+    /// 2. `[writable]` Project vault PDA
+    /// 3. `[writable]` Contribution account
+    /// 4. `[]` System program
+    /// 
+    /// Contributions with a receipt also pass:
+    /// - `[]` Signer's receipt token account
+    /// 
+    /// Token projects then also pass:
+    /// - `[]` Mint
+    /// - `[writable]` Escrow token account PDA
+    /// - `[]` Token program
+    /// - `[writable]` Signer's token account to refund into
+    /// 
+    /// Every project finally passes:
+    /// - `[writable]` Creator bond PDA
+    ClaimRefund {},
+    
+    /// Append a milestone to a project that has not received any votes yet
+    /// 
+    /// The project goal grows by the milestone amount, and the project account
+    /// is reallocated with the creator paying any extra rent. The creator bond
+    /// is topped up by the Config `creator_bond_bps` share of the added amount.
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Project creator
+    /// 1. `[writable]` Project account
+    /// 2. `[]` System program
+    /// 3. `[]` Config PDA
+    /// 4. `[writable]` Creator bond PDA
+    /// 
+    /// Token projects also pass:
+    /// 5. `[]` Mint
+    /// 6. `[]` Escrow token account PDA
+    /// 7. `[]` Token program
+    /// 8. `[writable]` Creator's token account funding the bond
+    AddMilestone {
+        milestone: MilestoneArgs,
+    },
+    
+    /// Create the program Config
+    /// 
+    /// Only the program's upgrade authority may create the Config, and it
+    /// becomes the Config admin.
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Program upgrade authority
+    /// 1. `[writable]` Config PDA, `[CONFIG_SEED]`
+    /// 2. `[]` Program data account of this program
+    /// 3. `[]` System program
+    InitializeConfig {
+        params: ConfigParams,
+    },
+    
+    /// Update the program Config, optionally handing it to a new admin
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer]` Config admin
+    /// 1. `[writable]` Config PDA
+    UpdateConfig {
+        new_admin: Option<Pubkey>,
+        params: ConfigParams,
+    },
+    
+    /// Close a project's funding period after `end_time`
+    /// 
+    /// Anyone may call this. The project becomes `Funded` if it met its
+    /// funding mode's target and `Failed` otherwise, which opens refunds.
+    /// 
+    /// Accounts expected:
+    /// 0. `[writable]` Project account
+    FinalizeFunding {},
+    
+    /// Vote on a milestone as a funder, weighted by the contribution amount
+    /// 
+    /// Each contribution votes once per validation round of a milestone, and a
+    /// new round clears the funder tallies. The milestone becomes
+    /// `Validated` only when the funder vote meets the Config quorum and
+    /// approval ratio and the validator threshold has also been reached.
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Contributor, or the receipt holder for
+    ///    contributions with a receipt
+    /// 1. `[writable]` Project account
+    /// 2. `[]` Config PDA
+    /// 3. `[]` Contribution account
+    /// 4. `[writable]` Milestone vote record PDA, `[MILESTONE_VOTE_SEED, contribution, milestone_index, round]`
+    /// 5. `[]` System program
+    /// 
+    /// Contributions with a receipt also pass:
+    /// 6. `[]` Signer's receipt token account
+    VoteMilestone {
+        milestone_index: u8,
+        approve: bool,
+    },
+    
+    /// Decide a project's approval vote after `voting_end`
+    /// 
+    /// Anyone may call this. The project becomes `Active` if the turnout met
+    /// the Config vote quorum and the approve share met the Config approval
+    /// ratio, and `Cancelled` otherwise. The funding period starts at the
+    /// tally and keeps the duration chosen at creation.
+    /// 
+    /// Accounts expected:
+    /// 0. `[writable]` Project account
+    /// 1. `[]` Config PDA
+    TallyVotes {},
+    
+    /// Register as a milestone validator, or add to an existing stake
+    /// 
+    /// Moves `amount` lamports into the validator's stake PDA, creating it on
+    /// first use. The resulting stake must meet the Config minimum.
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Validator
+    /// 1. `[writable]` Validator stake PDA, `[VALIDATOR_SEED, validator]`
+    /// 2. `[]` Config PDA
+    /// 3. `[]` System program
+    RegisterValidator {
+        amount: u64,
+    },
+    
+    /// Stop validating and start the Config unbonding period
+    /// 
+    /// The stake stays slashable until it is withdrawn.
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer]` Validator
+    /// 1. `[writable]` Validator stake PDA
+    /// 2. `[]` Config PDA
+    DeregisterValidator {},
+    
+    /// Withdraw a validator's remaining stake once unbonding has finished,
+    /// closing the stake account
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Validator
+    /// 1. `[writable]` Validator stake PDA
+    WithdrawValidatorStake {},
+    
+    /// Overturn a validated milestone
+    /// 
+    /// Only the Config admin may call this. The milestone returns to `Pending`
+    /// with its validations cleared and a new validation round, which makes
+    /// every approval from the overturned round slashable. A milestone
+    /// overturned `RESERVED_OVERTURNED_ROUNDS` times cancels the project into
+    /// refund, as does an upheld dispute that reaches the same count.
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Config admin, pays for any account growth
+    /// 1. `[]` Config PDA
+    /// 2. `[writable]` Project account
+    /// 3. `[]` System program
+    OverturnMilestone {
+        milestone_index: u8,
+    },
+    
+    /// Slash a validator for an approval from an overturned round
+    /// 
+    /// Anyone may call this. The Config slash share of the validator's stake
+    /// is paid to the Config fee recipient, once per validation.
+    /// 
+    /// Accounts expected:
+    /// 0. `[]` Project account
+    /// 1. `[]` Config PDA
+    /// 2. `[writable]` Validation record
+    /// 3. `[writable]` Validator stake PDA
+    /// 4. `[writable]` Fee recipient
+    SlashValidator {},
+    
+    /// Dispute a validated milestone during its challenge window
+    /// 
+    /// The signer must have contributed to the project or hold a contribution
+    /// receipt, and posts the Config dispute bond into the dispute PDA. The
+    /// milestone cannot be released while the dispute is open.
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Challenger
+    /// 1. `[writable]` Project account
+    /// 2. `[]` Config PDA
+    /// 3. `[]` Contribution account
+    /// 4. `[writable]` Dispute PDA, `[DISPUTE_SEED, project, milestone_index, round]`
+    /// 5. `[]` System program
+    /// 
+    /// Contributions with a receipt also pass:
+    /// 6. `[]` Signer's receipt token account
+    OpenDispute {
+        milestone_index: u8,
+        evidence_hash: [u8; 32],
+    },
+    
+    /// Vote on an open dispute as a funder, weighted by the contribution amount
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Contributor, or the receipt holder for
+    ///    contributions with a receipt
+    /// 1. `[]` Project account
+    /// 2. `[writable]` Dispute PDA
+    /// 3. `[]` Contribution account
+    /// 4. `[writable]` Dispute vote record PDA, `[DISPUTE_VOTE_SEED, dispute, contribution]`
+    /// 5. `[]` System program
+    /// 
+    /// Contributions with a receipt also pass:
+    /// 6. `[]` Signer's receipt token account
+    VoteDispute {
+        uphold: bool,
+    },
+    
+    /// Settle a dispute by its contributor vote after the voting period
+    /// 
+    /// Anyone may call this. The dispute is upheld if the vote met the Config
+    /// funder quorum and uphold weight outweighs dismiss weight. Upholding
+    /// overturns the milestone and returns the bond to the challenger;
+    /// dismissing confirms the milestone and pays the bond to the Config fee
+    /// recipient.
+    /// 
+    /// Accounts expected:
+    /// 0. `[writable]` Project account
+    /// 1. `[]` Config PDA
+    /// 2. `[writable]` Dispute PDA
+    /// 3. `[writable]` Challenger
+    /// 4. `[writable]` Fee recipient
+    /// 5. `[signer, writable]` Payer for any project account growth
+    /// 6. `[]` System program
+    ResolveDispute {},
+    
+    /// Settle a dispute by ruling of the Config admin, at any time
+    /// 
+    /// Accounts expected:
+    /// 0. `[writable]` Project account
+    /// 1. `[]` Config PDA
+    /// 2. `[writable]` Dispute PDA
+    /// 3. `[writable]` Challenger
+    /// 4. `[writable]` Fee recipient
+    /// 5. `[signer, writable]` Config admin, pays for any account growth
+    /// 6. `[]` System program
+    ArbitrateDispute {
+        uphold: bool,
+    },
+    
+    /// Attach proof of completion to a pending milestone
+    /// 
+    /// Only the project creator may call this. Entries are appended to the
+    /// milestone's evidence, up to `MAX_MILESTONE_EVIDENCE` per milestone,
+    /// until the first validation or rejection of the round, so every
+    /// validator in a round reviews the same evidence.
+    /// 
+    /// Submitting evidence for a `Rejected` milestone resubmits it: the old
+    /// evidence is replaced and the milestone returns to `Pending` in a new
+    /// validation round, up to the Config resubmission limit.
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Project creator, pays for any account growth
+    /// 1. `[writable]` Project account
+    /// 2. `[]` Config PDA
+    /// 3. `[]` System program
+    SubmitMilestoneEvidence {
+        milestone_index: u8,
+        evidence: Vec<MilestoneEvidence>,
+    },
+    
+    /// Mark a project `Defaulted` because a milestone is overdue
+    /// 
+    /// Anyone may call this once an unreleased milestone is past its due date
+    /// plus the Config grace period. Defaulted projects are refundable.
+    /// All-or-nothing projects must be finalized with `FinalizeFunding` first,
+    /// so a missed goal fails the project instead of defaulting it.
+    /// 
+    /// Accounts expected:
+    /// 0. `[writable]` Project account
+    /// 1. `[]` Config PDA
+    MarkDefault {
+        milestone_index: u8,
+    },
+    
+    /// Return the creator bond to the creator
+    /// 
+    /// Allowed once the project is `Completed` or `Failed`, or `Cancelled`
+    /// before it was ever activated. A bond forfeited by default or by
+    /// cancellation after activation is paid out through `ClaimRefund`
+    /// instead, unless the project never received a contribution.
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Project creator
+    /// 1. `[writable]` Project account
+    /// 2. `[]` Project vault PDA
+    /// 3. `[writable]` Creator bond PDA
+    /// 4. `[]` System program
+    /// 
+    /// Token projects also pass:
+    /// 5. `[]` Mint
+    /// 6. `[]` Escrow token account PDA
+    /// 7. `[]` Token program
+    /// 8. `[writable]` Creator's token account
+    ReclaimBond {},
 }
 
 impl ColabioInstruction {
-    /// Unpacks a byte buffer into a ColabioInstruction
+    /// Unpacks a versioned, Borsh-encoded byte buffer into a ColabioInstruction
     pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
-        let (tag, rest) = input.split_first().ok_or(ProgramError::InvalidInstructionData)?;
-        
-        Ok(match tag {
-            0 => {
-                let (title, rest) = Self::unpack_string(rest)?;
-                let (description, rest) = Self::unpack_string(rest)?;
-                let goal_amount = Self::unpack_u64(rest, 0)?;
-                let duration = Self::unpack_u64(rest, 8)?;
-                
-                // Unpack milestones
-                let milestones_len = rest[16] as usize;
-                let mut milestones = Vec::with_capacity(milestones_len);
-                let mut offset = 17;
-                
-                for _ in 0..milestones_len {
-                    let (name, new_rest) = Self::unpack_string(&rest[offset..])?;
-                    offset += name.len() + 4; // 4 bytes for string length
-                    
-                    let (description, _) = Self::unpack_string(&rest[offset..])?;
-                    offset += description.len() + 4;
-                    
-                    let amount = Self::unpack_u64(&rest, offset)?;
-                    offset += 8;
-                    
-                    milestones.push(Milestone {
-                        name,
-                        description,
-                        amount,
-                        completed: false,
-                        validations: 0,
-                    });
+        let (version, rest) = input.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+        
+        if *version != INSTRUCTION_VERSION {
+            return Err(ColabioError::UnsupportedInstructionVersion.into());
+        }
+        
+        Self::try_from_slice(rest).map_err(|_| ProgramError::InvalidInstructionData)
+    }
+    
+    /// Packs a ColabioInstruction into instruction data, including the version byte
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = vec![INSTRUCTION_VERSION];
+        // Writing into a Vec cannot fail
+        self.serialize(&mut data).unwrap();
+        data
+    }
+}
+
+/// Errors returned by the Colabio program
+/// 
+/// Each variant maps to `ProgramError::Custom(code)` with the discriminant as
+/// the code. Codes are part of the client interface: never reorder or reuse
+/// them, only append new variants.
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum ColabioError {
+    /// The account is not owned by the Colabio program
+    #[error("Account is not owned by the Colabio program")]
+    InvalidAccountOwner = 0,
+    /// The vault account is not the project's vault PDA
+    #[error("Vault account does not match the project vault")]
+    InvalidVault = 1,
+    /// The signer is not the project creator
+    #[error("Signer is not the project creator")]
+    NotProjectCreator = 2,
+    /// The project is not in the `Pending` state
+    #[error("Project is not pending approval")]
+    ProjectNotPending = 3,
+    /// The project is not in the `Active` state
+    #[error("Project is not active")]
+    ProjectNotActive = 4,
+    /// The project can no longer be cancelled
+    #[error("Project cannot be cancelled in its current state")]
+    ProjectNotCancellable = 5,
+    /// The project's funding period has ended
+    #[error("Funding period has ended")]
+    FundingPeriodEnded = 6,
+    /// The milestone index is out of range
+    #[error("Milestone index is out of range")]
+    InvalidMilestoneIndex = 7,
+    /// The milestone has not reached the `Validated` state
+    #[error("Milestone is not validated")]
+    MilestoneNotValidated = 8,
+    /// The contribution record belongs to a different project
+    #[error("Contribution does not belong to this project")]
+    ContributionMismatch = 9,
+    /// The contribution has already been refunded
+    #[error("Contribution has already been refunded")]
+    AlreadyRefunded = 10,
+    /// The project is not cancelled, failed, defaulted, or completed with escrow left
+    #[error("Project is not eligible for refunds")]
+    ProjectNotRefundable = 11,
+    /// The vote account is not the voter's vote record PDA
+    #[error("Vote account does not match the voter's vote record")]
+    InvalidVoteAccount = 12,
+    /// The stored vote record belongs to a different voter or project
+    #[error("Vote record belongs to a different voter or project")]
+    VoteRecordMismatch = 13,
+    /// The wallet has already voted on this project
+    #[error("Wallet has already voted on this project")]
+    AlreadyVoted = 14,
+    /// The validation account is not the validator's validation record PDA
+    #[error("Validation account does not match the validator's validation record")]
+    InvalidValidationAccount = 15,
+    /// The validator has already validated this milestone
+    #[error("Validator has already validated this milestone")]
+    AlreadyValidated = 16,
+    /// The project creator tried to validate their own milestone
+    #[error("Project creator cannot validate their own milestones")]
+    CreatorCannotValidate = 17,
+    /// The milestone's funds have already been released
+    #[error("Milestone funds have already been released")]
+    MilestoneAlreadyReleased = 18,
+    /// The milestone is no longer accepting validations
+    #[error("Milestone is not pending validation")]
+    MilestoneNotPending = 19,
+    /// The instruction data does not start with a supported version byte
+    #[error("Unsupported instruction version")]
+    UnsupportedInstructionVersion = 20,
+    /// The serialized project would exceed `MAX_PROJECT_SPACE`
+    #[error("Project data exceeds the maximum account size")]
+    ProjectTooLarge = 21,
+    /// The project has already received votes and can no longer be edited
+    #[error("Project can no longer be edited")]
+    ProjectLocked = 22,
+    /// The account holds a different type of Colabio state
+    #[error("Account discriminator does not match the expected account type")]
+    InvalidAccountType = 23,
+    /// The account was written with a layout version this program cannot read
+    #[error("Unsupported account layout version")]
+    UnsupportedAccountVersion = 24,
+    /// The account has already been initialized
+    #[error("Account is already initialized")]
+    AccountAlreadyInitialized = 25,
+    /// The config account is not the program's Config PDA
+    #[error("Config account does not match the program Config")]
+    InvalidConfigAccount = 26,
+    /// The signer is not the Config admin or program upgrade authority
+    #[error("Signer is not the Config admin")]
+    NotConfigAdmin = 27,
+    /// The Config parameters are inconsistent or out of range
+    #[error("Invalid Config parameters")]
+    InvalidConfigParams = 28,
+    /// The project duration is outside the Config bounds
+    #[error("Project duration is outside the allowed range")]
+    InvalidDuration = 29,
+    /// The fee recipient account does not match the Config
+    #[error("Fee recipient does not match the Config")]
+    InvalidFeeRecipient = 30,
+    /// The program data account is not this program's upgradeable data account
+    #[error("Invalid program data account")]
+    InvalidProgramData = 31,
+    /// The project's funding period has not ended yet
+    #[error("Funding period has not ended")]
+    FundingPeriodNotEnded = 32,
+    /// An all-or-nothing project must be finalized as funded before releases
+    #[error("Funding has not been finalized")]
+    FundingNotFinalized = 33,
+    /// The vault does not hold enough unreleased funds for the payout
+    #[error("Insufficient unreleased escrow")]
+    InsufficientEscrow = 34,
+    /// The mint account does not match the project's accepted mint
+    #[error("Mint does not match the project mint")]
+    InvalidMint = 35,
+    /// The token program account is not a supported token program
+    #[error("Unsupported token program")]
+    InvalidTokenProgram = 36,
+    /// A token account has the wrong mint or owner
+    #[error("Token account has the wrong mint or owner")]
+    InvalidTokenAccount = 37,
+    /// The signer neither made the contribution nor holds its receipt
+    #[error("Signer does not control this contribution")]
+    NotContributionHolder = 38,
+    /// The receipt mint is not the contribution's receipt mint PDA
+    #[error("Receipt mint does not match the contribution")]
+    InvalidReceiptMint = 39,
+    /// The milestone vote account is not the contribution's vote record PDA
+    #[error("Milestone vote account does not match the contribution's vote record")]
+    InvalidMilestoneVoteAccount = 40,
+    /// The contribution has already voted on this milestone
+    #[error("Contribution has already voted on this milestone")]
+    AlreadyVotedOnMilestone = 41,
+    /// The project's approval voting window is not open
+    #[error("Project voting window is not open")]
+    VotingClosed = 42,
+    /// The project's approval voting window has not ended
+    #[error("Project voting window has not ended")]
+    VotingNotEnded = 43,
+    /// The validator stake account is not the validator's stake PDA
+    #[error("Invalid validator stake account")]
+    InvalidValidatorStakeAccount = 44,
+    /// The signer is not an active registered validator
+    #[error("Validator is not registered")]
+    ValidatorNotRegistered = 45,
+    /// The validator's stake is below the Config minimum
+    #[error("Validator stake is below the required minimum")]
+    InsufficientValidatorStake = 46,
+    /// The validator has deregistered and is unbonding
+    #[error("Validator is unbonding")]
+    ValidatorUnbonding = 47,
+    /// The validator's stake cannot be withdrawn yet
+    #[error("Validator unbonding period has not completed")]
+    UnbondingNotComplete = 48,
+    /// The validation's round has not been overturned
+    #[error("Validation has not been overturned")]
+    ValidationNotOverturned = 49,
+    /// The validation has already been slashed
+    #[error("Validation has already been slashed")]
+    ValidationAlreadySlashed = 50,
+    /// The milestone has an open dispute
+    #[error("Milestone has an open dispute")]
+    MilestoneDisputed = 51,
+    /// The milestone is still inside its challenge window
+    #[error("Milestone challenge period has not ended")]
+    ChallengePeriodActive = 52,
+    /// The milestone's challenge window has closed
+    #[error("Milestone challenge period has ended")]
+    ChallengePeriodEnded = 53,
+    /// The dispute account is not the expected dispute PDA
+    #[error("Invalid dispute account")]
+    InvalidDisputeAccount = 54,
+    /// The dispute has already been resolved
+    #[error("Dispute is not open")]
+    DisputeNotOpen = 55,
+    /// The dispute's voting period has ended
+    #[error("Dispute voting period has ended")]
+    DisputeVotingClosed = 56,
+    /// The dispute's voting period has not ended
+    #[error("Dispute voting period has not ended")]
+    DisputeVotingNotEnded = 57,
+    /// The dispute vote account is not the contribution's vote record PDA
+    #[error("Invalid dispute vote account")]
+    InvalidDisputeVoteAccount = 58,
+    /// The contribution has already voted on this dispute
+    #[error("Contribution has already voted on this dispute")]
+    AlreadyVotedOnDispute = 59,
+    /// The milestone has no evidence to validate against
+    #[error("Milestone has no evidence")]
+    MissingEvidence = 60,
+    /// The reviewed evidence is not the milestone's latest evidence
+    #[error("Evidence hash does not match the milestone's latest evidence")]
+    EvidenceMismatch = 61,
+    /// The evidence submission is empty or exceeds the evidence limits
+    #[error("Invalid milestone evidence")]
+    InvalidEvidence = 62,
+    /// The milestone has used all of its resubmissions
+    #[error("Milestone resubmission limit reached")]
+    ResubmissionLimitReached = 63,
+    /// A milestone amount is zero
+    #[error("Milestone amount must be greater than zero")]
+    InvalidMilestoneAmount = 64,
+    /// Milestone amounts do not fit the goal under the remainder policy
+    #[error("Milestone amounts do not match the project goal")]
+    MilestoneBudgetMismatch = 65,
+    /// The project has no milestones or more than `MAX_MILESTONES`
+    #[error("Invalid number of milestones")]
+    InvalidMilestoneCount = 66,
+    /// A title, name or description is empty or over its length limit
+    #[error("Title, name or description has an invalid length")]
+    InvalidFieldLength = 67,
+    /// An amount, counter or timestamp calculation overflowed
+    #[error("Arithmetic overflow")]
+    ArithmeticOverflow = 68,
+    /// The cluster clock reported a time before the Unix epoch
+    #[error("Invalid clock timestamp")]
+    InvalidClock = 69,
+    /// The escrow ledger is inconsistent with the milestones or the escrow balance
+    #[error("Escrow ledger invariant violated")]
+    LedgerInvariantViolated = 70,
+    /// A milestone's prerequisites have not all been released
+    #[error("Milestone prerequisites have not been released")]
+    PrerequisitesNotReleased = 71,
+    /// A milestone dependency is not an earlier milestone, or is repeated
+    #[error("Invalid milestone dependency")]
+    InvalidMilestoneDependency = 72,
+    /// The milestone is not past its due date and grace period
+    #[error("Milestone is not overdue")]
+    MilestoneNotOverdue = 73,
+    /// The bond account is not the project's creator bond PDA
+    #[error("Invalid creator bond account")]
+    InvalidBondAccount = 74,
+    /// The creator bond cannot be returned in the project's current state
+    #[error("Creator bond is not reclaimable")]
+    BondNotReclaimable = 75,
+    /// A milestone is due at the end of funding, or before a milestone it
+    /// must follow
+    #[error("Invalid milestone due date")]
+    InvalidMilestoneDueDate = 76,
+    /// Validators have already reviewed the milestone's evidence this round
+    #[error("Milestone evidence is locked for this validation round")]
+    EvidenceLocked = 77,
+}
+
+impl From<ColabioError> for ProgramError {
+    fn from(e: ColabioError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for ColabioError {
+    fn type_of() -> &'static str {
+        "ColabioError"
+    }
+}
+
+impl PrintProgramError for ColabioError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + num_traits::FromPrimitive,
+    {
+        msg!("Error: {}", self);
+    }
+}
+
+/// Upper bound on a project account's size.
+///
+/// Project accounts are sized from their serialized data and grown with
+/// `realloc`, which can add at most `MAX_PERMITTED_DATA_INCREASE` bytes per
+/// instruction; capping the whole account at that size keeps creation and
+/// every later growth within a single instruction.
+pub const MAX_PROJECT_SPACE: usize = MAX_PERMITTED_DATA_INCREASE;
+
+/// Most milestones a project can have
+pub const MAX_MILESTONES: usize = 10;
+
+/// Longest project title, in bytes
+pub const MAX_TITLE_LEN: usize = 64;
+
+/// Longest project description, in bytes
+pub const MAX_DESCRIPTION_LEN: usize = 512;
+
+/// Longest milestone name, in bytes
+pub const MAX_MILESTONE_NAME_LEN: usize = 64;
+
+/// Longest milestone description, in bytes
+pub const MAX_MILESTONE_DESCRIPTION_LEN: usize = 256;
+
+/// Times a milestone can be overturned; the last overturn cancels the project
+/// into refund, so the overturned rounds always fit the project size budget
+pub const RESERVED_OVERTURNED_ROUNDS: usize = 4;
+
+// A project at every limit must still fit in its account
+const _: () = assert!(Project::MAX_LEN <= MAX_PROJECT_SPACE);
+
+/// Most evidence entries a single milestone can hold
+pub const MAX_MILESTONE_EVIDENCE: usize = 4;
+
+/// Longest evidence URI, in bytes
+pub const MAX_EVIDENCE_URI_LEN: usize = 80;
+
+/// Discriminator stored in the first byte of every program account
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum AccountType {
+    Uninitialized,
+    Project,
+    Contribution,
+    Validation,
+    Vote,
+    Config,
+    MilestoneVote,
+    ValidatorStake,
+    Dispute,
+    DisputeVote,
+}
+
+/// State stored in a program-owned account.
+///
+/// Every state struct starts with an `AccountType` discriminator followed by
+/// a layout version byte, so `load` can refuse accounts of the wrong type or
+/// layout before trusting any of the data.
+pub trait ColabioAccount: BorshDeserialize {
+    /// Discriminator written to byte 0
+    const ACCOUNT_TYPE: AccountType;
+    /// Current layout version written to byte 1
+    const VERSION: u8;
+    
+    /// Deserializes the account after checking owner, discriminator and version
+    fn load(account: &AccountInfo, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if account.owner != program_id {
+            return Err(ColabioError::InvalidAccountOwner.into());
+        }
+        
+        let data = account.data.borrow();
+        match data.get(..2) {
+            Some([account_type, version]) => {
+                if *account_type != Self::ACCOUNT_TYPE as u8 {
+                    return Err(ColabioError::InvalidAccountType.into());
                 }
-                
-                Self::InitializeProject {
-                    title,
-                    description,
-                    goal_amount,
-                    duration,
-                    milestones,
+                if *version != Self::VERSION {
+                    return Err(ColabioError::UnsupportedAccountVersion.into());
                 }
             }
-            1 => {
-                let amount = Self::unpack_u64(rest, 0)?;
-                Self::Contribute { amount }
-            }
-            2 => {
-                let milestone_index = rest[0];
-                Self::ValidateMilestone { milestone_index }
-            }
-            3 => {
-                let milestone_index = rest[0];
-                Self::ReleaseFunds { milestone_index }
-            }
-            4 => Self::CancelProject {},
-            5 => {
-                let approve = rest[0] != 0;
-                Self::Vote { approve }
-            }
-            _ => return Err(ProgramError::InvalidInstructionData),
-        })
+            _ => return Err(ColabioError::InvalidAccountType.into()),
+        }
+        
+        Ok(Self::deserialize(&mut &data[..])?)
     }
+}
+
+/// Governance settings shared by every project
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct ConfigParams {
+    /// Minimum number of votes before a project can be decided
+    pub vote_quorum: u32,
+    /// Share of approve votes, in basis points, needed to activate a project
+    pub approval_ratio_bps: u16,
+    /// Number of validations a milestone needs before it can be validated
+    pub required_validations: u32,
+    /// Shortest allowed funding duration, in seconds
+    pub min_duration: u64,
+    /// Longest allowed funding duration, in seconds
+    pub max_duration: u64,
+    /// Fee taken from each milestone release, in basis points
+    pub fee_bps: u16,
+    /// Account that receives release fees
+    pub fee_recipient: Pubkey,
+    /// Share of a project's raised amount, in basis points, that must take
+    /// part in a milestone's funder vote
+    pub funder_quorum_bps: u16,
+    /// Share of funder vote weight, in basis points, that must approve a
+    /// milestone
+    pub funder_approval_ratio_bps: u16,
+    /// Length of a project's approval voting window, in seconds
+    pub voting_period: u64,
+    /// Smallest stake, in lamports, a validator must bond to validate
+    pub min_validator_stake: u64,
+    /// Time a deregistered validator's stake stays slashable, in seconds; at
+    /// least the challenge period plus the dispute period
+    pub unbonding_period: u64,
+    /// Share of a validator's stake, in basis points, slashed per overturned
+    /// validation
+    pub slash_bps: u16,
+    /// Time after a milestone is validated during which it can be disputed,
+    /// in seconds
+    pub challenge_period: u64,
+    /// Lamports a contributor must post to open a dispute
+    pub dispute_bond: u64,
+    /// Length of a dispute's contributor vote, in seconds
+    pub dispute_period: u64,
+    /// Number of reject validations that rejects a milestone
+    pub rejection_threshold: u32,
+    /// Times a creator may resubmit a rejected milestone
+    pub max_resubmissions: u32,
+    /// Time past a milestone's due date before the project can be marked
+    /// defaulted, in seconds
+    pub milestone_grace_period: u64,
+    /// Creator bond, as a share of the project goal in basis points
+    pub creator_bond_bps: u16,
+}
+
+impl ConfigParams {
+    /// Serialized size of the parameters
+    pub const LEN: usize =
+        4 + 2 + 4 + 8 + 8 + 2 + 32 + 2 + 2 + 8 + 8 + 8 + 2 + 8 + 8 + 8 + 4 + 4 + 8 + 2;
     
-    fn unpack_string(input: &[u8]) -> Result<(String, &[u8]), ProgramError> {
-        let str_len = u32::from_le_bytes(
-            input.get(..4)
-                .ok_or(ProgramError::InvalidInstructionData)?
-                .try_into()
-                .unwrap()
-        ) as usize;
-        
-        let str_data = input.get(4..4+str_len)
-            .ok_or(ProgramError::InvalidInstructionData)?;
-            
-        let str_value = String::from_utf8(str_data.to_vec())
-            .map_err(|_| ProgramError::InvalidInstructionData)?;
-            
-        Ok((str_value, &input[4+str_len..]))
+    /// Checks that the parameters are internally consistent
+    pub fn validate(&self) -> ProgramResult {
+        if self.vote_quorum == 0
+            || self.approval_ratio_bps as u64 > BPS_DENOMINATOR
+            || self.required_validations == 0
+            || self.min_duration == 0
+            || self.min_duration > self.max_duration
+            || self.fee_bps as u64 > BPS_DENOMINATOR
+            || self.funder_quorum_bps as u64 > BPS_DENOMINATOR
+            || self.funder_approval_ratio_bps as u64 > BPS_DENOMINATOR
+            || self.voting_period == 0
+            || self.min_validator_stake == 0
+            || self.unbonding_period == 0
+            || (self.unbonding_period as u128)
+                < self.challenge_period as u128 + self.dispute_period as u128
+            || self.slash_bps as u64 > BPS_DENOMINATOR
+            || self.dispute_period == 0
+            || self.rejection_threshold == 0
+            || self.creator_bond_bps as u64 > BPS_DENOMINATOR
+        {
+            return Err(ColabioError::InvalidConfigParams.into());
+        }
+        Ok(())
     }
     
-    fn unpack_u64(input: &[u8], start: usize) -> Result<u64, ProgramError> {
-        let bytes = input.get(start..start+8)
-            .ok_or(ProgramError::InvalidInstructionData)?
-            .try_into()
-            .unwrap();
-        Ok(u64::from_le_bytes(bytes))
+    /// Creator bond owed for a project goal
+    pub fn creator_bond(&self, goal_amount: u64) -> u64 {
+        (goal_amount as u128 * self.creator_bond_bps as u128 / BPS_DENOMINATOR as u128) as u64
     }
 }
 
 // Define the program's account structures
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct Project {
+    pub account_type: AccountType,
+    pub version: u8,
     pub creator: Pubkey,
     pub title: String,
     pub description: String,
     pub goal_amount: u64,
-    pub raised_amount: u64,
+    pub ledger: EscrowLedger,
     pub start_time: u64,
     pub end_time: u64,
     pub milestones: Vec<Milestone>,
     pub status: ProjectStatus,
     pub approve_votes: u32,
     pub reject_votes: u32,
+    pub vault_bump: u8,
+    pub funding_mode: FundingMode,
+    pub mint: Option<Pubkey>,
+    pub vault_token_bump: u8,
+    pub voting_start: u64,
+    pub voting_end: u64,
+    pub remainder_policy: RemainderPolicy,
+    pub sequential_milestones: bool,
+    /// When the approval vote activated the project, or 0 if it never did
+    pub activated_at: u64,
+    pub bond_bump: u8,
+    /// Net creator bond deposited at creation
+    pub creator_bond: u64,
+    /// Bond paid back to the creator or out to contributors so far
+    pub bond_paid: u64,
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
-pub struct Milestone {
-    pub name: String,
-    pub description: String,
-    pub amount: u64,
-    pub completed: bool,
-    pub validations: u32,
+impl Project {
+    /// Largest serialized size within the limits: header, creator, title,
+    /// description, goal, ledger, start and end times, milestones, status,
+    /// votes, vault bump, funding mode, mint, vault token bump, voting window,
+    /// remainder policy, sequential flag, activated_at, bond bump and bond
+    pub const MAX_LEN: usize = 2 + 32 + (4 + MAX_TITLE_LEN) + (4 + MAX_DESCRIPTION_LEN) + 8 + 32 + 8 + 8
+        + (4 + MAX_MILESTONES * Milestone::MAX_LEN)
+        + 1 + 4 + 4 + 1 + 1 + 33 + 1 + 8 + 8 + 1 + 1 + 8 + 1 + 8 + 8;
+    
+    /// When the given milestone falls due; the schedule follows the funding
+    /// period, so it moves with `end_time` when the vote is tallied
+    pub fn milestone_due_date(&self, milestone_index: usize) -> Result<u64, ProgramError> {
+        Ok(self
+            .end_time
+            .checked_add(self.milestones[milestone_index].due_after)
+            .ok_or(ColabioError::ArithmeticOverflow)?)
+    }
+    
+    /// Whether every milestone the given milestone depends on, explicitly or
+    /// through sequential ordering, has been released
+    pub fn prerequisites_released(&self, milestone_index: usize) -> bool {
+        let released = |index: usize| {
+            self.milestones
+                .get(index)
+                .is_some_and(|milestone| milestone.status == MilestoneStatus::Released)
+        };
+        let sequential_ok = !self.sequential_milestones || (0..milestone_index).all(released);
+        sequential_ok
+            && self.milestones[milestone_index]
+                .dependencies
+                .iter()
+                .all(|dependency| released(*dependency as usize))
+    }
+    
+    /// Checks that the ledger never paid out more than it took in, and that
+    /// milestone payouts and budgets agree with it
+    pub fn check_invariants(&self) -> ProgramResult {
+        let violated = |_| ColabioError::LedgerInvariantViolated;
+        self.ledger.held().map_err(violated)?;
+        
+        let mut milestone_budget: u64 = 0;
+        let mut milestone_released: u64 = 0;
+        for milestone in &self.milestones {
+            if milestone.released_amount > milestone.amount {
+                return Err(ColabioError::LedgerInvariantViolated.into());
+            }
+            milestone_budget = milestone_budget
+                .checked_add(milestone.amount)
+                .ok_or(ColabioError::LedgerInvariantViolated)?;
+            milestone_released = milestone_released
+                .checked_add(milestone.released_amount)
+                .ok_or(ColabioError::LedgerInvariantViolated)?;
+        }
+        
+        if milestone_budget > self.goal_amount
+            || milestone_released != self.ledger.paid_out().map_err(violated)?
+            || self.bond_paid > self.creator_bond
+        {
+            return Err(ColabioError::LedgerInvariantViolated.into());
+        }
+        Ok(())
+    }
+    
+    /// Whether the creator bond goes to contributors rather than back to the
+    /// creator
+    pub fn bond_forfeited(&self) -> bool {
+        let forfeited = match self.status {
+            ProjectStatus::Defaulted => true,
+            ProjectStatus::Cancelled => self.activated_at != 0,
+            _ => false,
+        };
+        // With nobody to distribute it to, the bond goes back to the creator
+        forfeited && self.ledger.total_deposited > 0
+    }
+    
+    /// Whether the creator may take the bond back
+    pub fn bond_reclaimable(&self) -> bool {
+        let settled = matches!(
+            self.status,
+            ProjectStatus::Completed
+                | ProjectStatus::Failed
+                | ProjectStatus::Cancelled
+                | ProjectStatus::Defaulted
+        );
+        settled && !self.bond_forfeited() && self.bond_paid == 0
+    }
+    
+    /// Whether contributors may claim their share of the remaining escrow
+    pub fn is_refundable(&self) -> bool {
+        match self.status {
+            ProjectStatus::Cancelled | ProjectStatus::Failed | ProjectStatus::Defaulted => true,
+            // Completed projects return whatever the milestones left behind
+            ProjectStatus::Completed => matches!(self.ledger.unreleased(), Ok(unreleased) if unreleased > 0),
+            _ => false,
+        }
+    }
+    
+    /// Overturns the given milestone, cancelling the project into refund once
+    /// the milestone has been overturned `RESERVED_OVERTURNED_ROUNDS` times
+    pub fn overturn_milestone(&mut self, milestone_index: usize) -> ProgramResult {
+        let milestone = &mut self.milestones[milestone_index];
+        milestone.overturn()?;
+        if milestone.overturned_rounds.len() >= RESERVED_OVERTURNED_ROUNDS {
+            self.status = ProjectStatus::Cancelled;
+            msg!("Milestone {} overturned too often, project cancelled", milestone_index);
+        }
+        Ok(())
+    }
+    
+    /// Whether milestone funds may be paid out in the current status
+    pub fn can_release_funds(&self) -> bool {
+        match self.status {
+            ProjectStatus::Funded => true,
+            ProjectStatus::Active => self.funding_mode == FundingMode::Flexible,
+            _ => false,
+        }
+    }
+}
+
+/// Running totals of everything that has moved through a project's escrow
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct EscrowLedger {
+    /// Net amount received from contributors
+    pub total_deposited: u64,
+    /// Amount paid to the creator for released milestones, after fees
+    pub total_released: u64,
+    /// Amount refunded to contributors
+    pub total_refunded: u64,
+    /// Platform fees taken from milestone releases
+    pub total_fees: u64,
+}
+
+impl EscrowLedger {
+    /// Records a contribution received by the escrow
+    pub fn deposit(&mut self, amount: u64) -> ProgramResult {
+        self.total_deposited = self.total_deposited
+            .checked_add(amount)
+            .ok_or(ColabioError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Records a milestone payout, split into the creator's share and the fee
+    pub fn release(&mut self, amount: u64, fee: u64) -> ProgramResult {
+        self.total_released = self.total_released
+            .checked_add(amount)
+            .ok_or(ColabioError::ArithmeticOverflow)?;
+        self.total_fees = self.total_fees.checked_add(fee).ok_or(ColabioError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Records a refund paid to a contributor
+    pub fn refund(&mut self, amount: u64) -> ProgramResult {
+        self.total_refunded = self.total_refunded
+            .checked_add(amount)
+            .ok_or(ColabioError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Gross amount paid out for milestones, fees included
+    pub fn paid_out(&self) -> Result<u64, ProgramError> {
+        Ok(self.total_released.checked_add(self.total_fees).ok_or(ColabioError::ArithmeticOverflow)?)
+    }
+    
+    /// Deposits not yet paid out for milestones; refunds are shared pro rata
+    /// against this amount
+    pub fn unreleased(&self) -> Result<u64, ProgramError> {
+        Ok(self.total_deposited.checked_sub(self.paid_out()?).ok_or(ColabioError::ArithmeticOverflow)?)
+    }
+    
+    /// Amount the escrow should still hold
+    pub fn held(&self) -> Result<u64, ProgramError> {
+        Ok(self.unreleased()?.checked_sub(self.total_refunded).ok_or(ColabioError::ArithmeticOverflow)?)
+    }
+    
+    /// A contribution's pro rata share of `pool`, rounded down so the shares
+    /// of every contribution never add up to more than the pool
+    pub fn pro_rata_share(&self, contribution_amount: u64, pool: u64) -> u64 {
+        if self.total_deposited == 0 {
+            return 0;
+        }
+        (contribution_amount as u128 * pool as u128 / self.total_deposited as u128) as u64
+    }
+}
+
+impl ColabioAccount for Project {
+    const ACCOUNT_TYPE: AccountType = AccountType::Project;
+    const VERSION: u8 = 1;
+}
+
+/// Project definition supplied by the creator in `InitializeProject`
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ProjectArgs {
+    pub title: String,
+    pub description: String,
+    pub goal_amount: u64,
+    /// Funding period in seconds, within the Config duration bounds
+    pub duration: u64,
+    pub milestones: Vec<MilestoneArgs>,
+    pub funding_mode: FundingMode,
+    /// SPL Token or Token-2022 mint accepted for contributions, or `None`
+    /// for native SOL
+    pub mint: Option<Pubkey>,
+    pub remainder_policy: RemainderPolicy,
+    /// Whether each milestone requires every earlier milestone to be released
+    pub sequential_milestones: bool,
+}
+
+impl ProjectArgs {
+    /// Checks the project definition against the creation limits and the
+    /// Config duration bounds
+    pub fn validate(&self, params: &ConfigParams) -> ProgramResult {
+        if self.title.is_empty()
+            || self.title.len() > MAX_TITLE_LEN
+            || self.description.len() > MAX_DESCRIPTION_LEN
+        {
+            return Err(ColabioError::InvalidFieldLength.into());
+        }
+        
+        if self.duration == 0
+            || self.duration < params.min_duration
+            || self.duration > params.max_duration
+        {
+            return Err(ColabioError::InvalidDuration.into());
+        }
+        
+        if self.milestones.is_empty() || self.milestones.len() > MAX_MILESTONES {
+            return Err(ColabioError::InvalidMilestoneCount.into());
+        }
+        
+        let due_after: Vec<u64> = self.milestones.iter().map(|milestone| milestone.due_after).collect();
+        let mut total: u64 = 0;
+        for (index, milestone) in self.milestones.iter().enumerate() {
+            milestone.validate(&due_after[..index], self.sequential_milestones)?;
+            total = total
+                .checked_add(milestone.amount)
+                .ok_or(ColabioError::MilestoneBudgetMismatch)?;
+        }
+        
+        // Milestones may never promise more than the goal; any shortfall must
+        // be covered by the declared remainder policy
+        let budget_ok = match self.remainder_policy {
+            RemainderPolicy::Exact => total == self.goal_amount,
+            RemainderPolicy::RefundOnCompletion => total <= self.goal_amount,
+        };
+        if !budget_ok {
+            return Err(ColabioError::MilestoneBudgetMismatch.into());
+        }
+        Ok(())
+    }
+}
+
+/// Milestone definition supplied by the creator in `InitializeProject`
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MilestoneArgs {
+    pub name: String,
+    pub description: String,
+    pub amount: u64,
+    /// Indices of milestones that must be released before this one
+    pub dependencies: Vec<u8>,
+    /// Seconds after the funding period's `end_time` by which the milestone
+    /// must be released; non-zero, and no earlier than its dependencies or,
+    /// in sequential projects, the milestone before it
+    pub due_after: u64,
+}
+
+impl MilestoneArgs {
+    /// Checks the milestone against the creation limits, given the `due_after`
+    /// of every milestone before it in the project
+    pub fn validate(&self, earlier_due_after: &[u64], sequential: bool) -> ProgramResult {
+        let index = earlier_due_after.len();
+        if self.name.is_empty()
+            || self.name.len() > MAX_MILESTONE_NAME_LEN
+            || self.description.len() > MAX_MILESTONE_DESCRIPTION_LEN
+        {
+            return Err(ColabioError::InvalidFieldLength.into());
+        }
+        if self.amount == 0 {
+            return Err(ColabioError::InvalidMilestoneAmount.into());
+        }
+        
+        // Dependencies may only point backwards, which rules out cycles
+        for (position, dependency) in self.dependencies.iter().enumerate() {
+            if *dependency as usize >= index || self.dependencies[..position].contains(dependency) {
+                return Err(ColabioError::InvalidMilestoneDependency.into());
+            }
+        }
+        
+        // A milestone needs time for delivery after funding closes, and can't
+        // fall due before the milestones it has to wait for
+        let after_sequence = !sequential || self.due_after >= earlier_due_after.last().copied().unwrap_or(0);
+        let after_dependencies = self
+            .dependencies
+            .iter()
+            .all(|dependency| self.due_after >= earlier_due_after[*dependency as usize]);
+        if self.due_after == 0 || !after_sequence || !after_dependencies {
+            return Err(ColabioError::InvalidMilestoneDueDate.into());
+        }
+        Ok(())
+    }
+}
+
+/// What happens to escrow that milestones do not account for
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum RemainderPolicy {
+    /// Milestone amounts must add up to exactly `goal_amount`
+    Exact,
+    /// Milestone amounts may add up to less than `goal_amount`; whatever is
+    /// left in escrow once every milestone is released is refunded pro rata
+    RefundOnCompletion,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Milestone {
+    pub name: String,
+    pub description: String,
+    pub amount: u64,
+    pub dependencies: Vec<u8>,
+    pub due_after: u64,
+    pub status: MilestoneStatus,
+    pub validations: u32,
+    pub released_at: u64,
+    pub released_amount: u64,
+    pub funder_approve_weight: u64,
+    pub funder_reject_weight: u64,
+    pub round: u32,
+    pub validated_at: u64,
+    pub disputed: bool,
+    pub evidence: Vec<MilestoneEvidence>,
+    pub rejections: u32,
+    pub resubmissions: u32,
+    /// Validation rounds ended by an overturn, whose approvals are slashable
+    pub overturned_rounds: Vec<u32>,
+}
+
+impl Milestone {
+    /// Largest serialized size of a milestone within the limits, with full
+    /// evidence and room for `RESERVED_OVERTURNED_ROUNDS` overturned rounds
+    pub const MAX_LEN: usize = (4 + MAX_MILESTONE_NAME_LEN) + (4 + MAX_MILESTONE_DESCRIPTION_LEN) + 8
+        + (4 + MAX_MILESTONES - 1) + 8 + 1 + 4 + 8 + 8 + 8 + 8 + 4 + 8 + 1
+        + (4 + MAX_MILESTONE_EVIDENCE * MilestoneEvidence::MAX_LEN) + 4 + 4
+        + (4 + RESERVED_OVERTURNED_ROUNDS * 4);
+    
+    /// Whether the weighted funder vote meets the Config quorum and ratio
+    pub fn funder_vote_passed(&self, params: &ConfigParams, raised_amount: u64) -> bool {
+        let total_weight = self.funder_approve_weight as u128 + self.funder_reject_weight as u128;
+        let quorum_met = total_weight * BPS_DENOMINATOR as u128
+            >= raised_amount as u128 * params.funder_quorum_bps as u128;
+        let ratio_met = total_weight > 0
+            && self.funder_approve_weight as u128 * BPS_DENOMINATOR as u128
+                >= total_weight * params.funder_approval_ratio_bps as u128;
+        quorum_met && ratio_met
+    }
+    
+    /// Marks a pending milestone `Validated` once both the validator
+    /// threshold and the funder vote have passed, opening its challenge window
+    pub fn refresh_status(&mut self, params: &ConfigParams, raised_amount: u64, now: u64) {
+        if self.status == MilestoneStatus::Pending
+            && self.validations >= params.required_validations
+            && self.funder_vote_passed(params, raised_amount)
+        {
+            self.status = MilestoneStatus::Validated;
+            self.validated_at = now;
+        }
+    }
+    
+    /// Sends the milestone back to `Pending` in a new validation round, which
+    /// makes every approval from the previous round slashable
+    pub fn overturn(&mut self) -> ProgramResult {
+        self.overturned_rounds.push(self.round);
+        self.start_round()
+    }
+    
+    /// Clears the validator and funder tallies and starts a new validation
+    /// round
+    fn start_round(&mut self) -> ProgramResult {
+        self.status = MilestoneStatus::Pending;
+        self.validations = 0;
+        self.rejections = 0;
+        self.funder_approve_weight = 0;
+        self.funder_reject_weight = 0;
+        self.validated_at = 0;
+        self.disputed = false;
+        self.round = self.round.checked_add(1).ok_or(ColabioError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+impl From<MilestoneArgs> for Milestone {
+    fn from(args: MilestoneArgs) -> Self {
+        Milestone {
+            name: args.name,
+            description: args.description,
+            amount: args.amount,
+            dependencies: args.dependencies,
+            due_after: args.due_after,
+            status: MilestoneStatus::Pending,
+            validations: 0,
+            released_at: 0,
+            released_amount: 0,
+            funder_approve_weight: 0,
+            funder_reject_weight: 0,
+            round: 0,
+            validated_at: 0,
+            disputed: false,
+            evidence: Vec::new(),
+            rejections: 0,
+            resubmissions: 0,
+            overturned_rounds: Vec::new(),
+        }
+    }
+}
+
+/// Proof of a milestone's completion submitted by the project creator
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct MilestoneEvidence {
+    /// SHA-256 digest or IPFS CID multihash digest of the evidence content
+    pub hash: [u8; 32],
+    /// Where the evidence can be fetched, such as an `ipfs://` or `https://` URI
+    pub uri: String,
+}
+
+impl MilestoneEvidence {
+    /// Largest serialized size: hash and a URI at `MAX_EVIDENCE_URI_LEN`
+    pub const MAX_LEN: usize = 32 + (4 + MAX_EVIDENCE_URI_LEN);
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum MilestoneStatus {
+    Pending,
+    Validated,
+    Released,
+    Rejected,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub enum ProjectStatus {
+    Pending,
+    Active,
+    Completed,
+    Cancelled,
+    Funded,
+    Failed,
+    Defaulted,
+}
+
+/// How a project's funding period is settled by `FinalizeFunding`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum FundingMode {
+    /// Funds are only released if `goal_amount` is reached by `end_time`;
+    /// otherwise the project fails and every contributor is refunded
+    AllOrNothing,
+    /// The creator keeps whatever is raised and may release validated
+    /// milestones while funding is still open
+    Flexible,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Contribution {
+    pub account_type: AccountType,
+    pub version: u8,
+    pub contributor: Pubkey,
+    pub project: Pubkey,
+    pub amount: u64,
+    pub timestamp: u64,
+    pub refunded: bool,
+    pub mint: Option<Pubkey>,
+    pub receipt_mint: Option<Pubkey>,
+}
+
+impl Contribution {
+    /// Serialized size: header, contributor, project, amount, timestamp,
+    /// refunded, mint, receipt_mint
+    pub const LEN: usize = 2 + 32 + 32 + 8 + 8 + 1 + (1 + 32) + (1 + 32);
+}
+
+impl ColabioAccount for Contribution {
+    const ACCOUNT_TYPE: AccountType = AccountType::Contribution;
+    const VERSION: u8 = 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Validation {
+    pub account_type: AccountType,
+    pub version: u8,
+    pub validator: Pubkey,
+    pub project: Pubkey,
+    pub milestone_index: u8,
+    pub round: u32,
+    pub evidence_hash: [u8; 32],
+    pub approve: bool,
+    pub timestamp: u64,
+    pub slashed: bool,
+    pub bump: u8,
+}
+
+impl Validation {
+    /// Serialized size: header, validator, project, milestone_index, round,
+    /// evidence_hash, approve, timestamp, slashed, bump
+    pub const LEN: usize = 2 + 32 + 32 + 1 + 4 + 32 + 1 + 8 + 1 + 1;
+}
+
+impl ColabioAccount for Validation {
+    const ACCOUNT_TYPE: AccountType = AccountType::Validation;
+    const VERSION: u8 = 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Vote {
+    pub account_type: AccountType,
+    pub version: u8,
+    pub voter: Pubkey,
+    pub project: Pubkey,
+    pub approve: bool,
+    pub timestamp: u64,
+    pub bump: u8,
+}
+
+impl Vote {
+    /// Serialized size: header, voter, project, approve, timestamp, bump
+    pub const LEN: usize = 2 + 32 + 32 + 1 + 8 + 1;
+}
+
+impl ColabioAccount for Vote {
+    const ACCOUNT_TYPE: AccountType = AccountType::Vote;
+    const VERSION: u8 = 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Config {
+    pub account_type: AccountType,
+    pub version: u8,
+    pub admin: Pubkey,
+    pub params: ConfigParams,
+    pub bump: u8,
+}
+
+impl Config {
+    /// Serialized size: header, admin, params, bump
+    pub const LEN: usize = 2 + 32 + ConfigParams::LEN + 1;
+    
+    /// Loads the Config, checking it lives at the Config PDA
+    pub fn load_checked(account: &AccountInfo, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        let config = Self::load(account, program_id)?;
+        let config_key = Pubkey::create_program_address(&[CONFIG_SEED, &[config.bump]], program_id)
+            .map_err(|_| ColabioError::InvalidConfigAccount)?;
+        if config_key != *account.key {
+            return Err(ColabioError::InvalidConfigAccount.into());
+        }
+        Ok(config)
+    }
+}
+
+impl ColabioAccount for Config {
+    const ACCOUNT_TYPE: AccountType = AccountType::Config;
+    const VERSION: u8 = 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MilestoneVote {
+    pub account_type: AccountType,
+    pub version: u8,
+    pub contribution: Pubkey,
+    pub project: Pubkey,
+    pub milestone_index: u8,
+    pub round: u32,
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub weight: u64,
+    pub timestamp: u64,
+    pub bump: u8,
+}
+
+impl MilestoneVote {
+    /// Serialized size: header, contribution, project, milestone_index, round,
+    /// voter, approve, weight, timestamp, bump
+    pub const LEN: usize = 2 + 32 + 32 + 1 + 4 + 32 + 1 + 8 + 8 + 1;
+}
+
+impl ColabioAccount for MilestoneVote {
+    const ACCOUNT_TYPE: AccountType = AccountType::MilestoneVote;
+    const VERSION: u8 = 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ValidatorStake {
+    pub account_type: AccountType,
+    pub version: u8,
+    pub validator: Pubkey,
+    /// Bonded lamports, excluding the account's rent reserve
+    pub stake: u64,
+    /// When the stake may be withdrawn, or 0 while the validator is active
+    pub unbonding_end: u64,
+    pub bump: u8,
+}
+
+impl ValidatorStake {
+    /// Serialized size: header, validator, stake, unbonding_end, bump
+    pub const LEN: usize = 2 + 32 + 8 + 8 + 1;
+    
+    /// Loads a validator's stake, checking it lives at the validator's stake PDA
+    pub fn load_checked(
+        account: &AccountInfo,
+        program_id: &Pubkey,
+        validator: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let stake = Self::load(account, program_id)?;
+        let stake_key = Pubkey::create_program_address(
+            &[VALIDATOR_SEED, validator.as_ref(), &[stake.bump]],
+            program_id,
+        )
+        .map_err(|_| ColabioError::InvalidValidatorStakeAccount)?;
+        if stake_key != *account.key || stake.validator != *validator {
+            return Err(ColabioError::InvalidValidatorStakeAccount.into());
+        }
+        Ok(stake)
+    }
+}
+
+impl ColabioAccount for ValidatorStake {
+    const ACCOUNT_TYPE: AccountType = AccountType::ValidatorStake;
+    const VERSION: u8 = 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum DisputeStatus {
+    Open,
+    Upheld,
+    Dismissed,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Dispute {
+    pub account_type: AccountType,
+    pub version: u8,
+    pub project: Pubkey,
+    pub milestone_index: u8,
+    pub round: u32,
+    pub challenger: Pubkey,
+    pub evidence_hash: [u8; 32],
+    /// Lamports held in the dispute account on top of its rent reserve
+    pub bond: u64,
+    pub opened_at: u64,
+    pub voting_end: u64,
+    pub uphold_weight: u64,
+    pub dismiss_weight: u64,
+    pub status: DisputeStatus,
+    pub bump: u8,
+}
+
+impl Dispute {
+    /// Serialized size: header, project, milestone_index, round, challenger,
+    /// evidence_hash, bond, opened_at, voting_end, uphold_weight,
+    /// dismiss_weight, status, bump
+    pub const LEN: usize = 2 + 32 + 1 + 4 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 1;
+}
+
+impl ColabioAccount for Dispute {
+    const ACCOUNT_TYPE: AccountType = AccountType::Dispute;
+    const VERSION: u8 = 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct DisputeVote {
+    pub account_type: AccountType,
+    pub version: u8,
+    pub dispute: Pubkey,
+    pub contribution: Pubkey,
+    pub voter: Pubkey,
+    pub uphold: bool,
+    pub weight: u64,
+    pub timestamp: u64,
+    pub bump: u8,
+}
+
+impl DisputeVote {
+    /// Serialized size: header, dispute, contribution, voter, uphold, weight,
+    /// timestamp, bump
+    pub const LEN: usize = 2 + 32 + 32 + 32 + 1 + 8 + 8 + 1;
+}
+
+impl ColabioAccount for DisputeVote {
+    const ACCOUNT_TYPE: AccountType = AccountType::DisputeVote;
+    const VERSION: u8 = 1;
+}
+
+/// Refuses program-owned accounts that already carry a discriminator
+fn check_uninitialized(account: &AccountInfo, program_id: &Pubkey) -> ProgramResult {
+    if account.owner == program_id
+        && account.data.borrow().first().copied().unwrap_or(0) != AccountType::Uninitialized as u8
+    {
+        return Err(ColabioError::AccountAlreadyInitialized.into());
+    }
+    Ok(())
+}
+
+/// Reads the upgrade authority out of this program's `ProgramData` account
+fn upgrade_authority(
+    program_id: &Pubkey,
+    program_data_account: &AccountInfo,
+) -> Result<Option<Pubkey>, ProgramError> {
+    let (program_data_key, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    if program_data_key != *program_data_account.key
+        || *program_data_account.owner != bpf_loader_upgradeable::id()
+    {
+        return Err(ColabioError::InvalidProgramData.into());
+    }
+    
+    // ProgramData is bincode encoded: a u32 variant tag of 3, the u64 deploy
+    // slot, then an Option<Pubkey> holding the upgrade authority
+    let data = program_data_account.data.borrow();
+    match data.get(..45) {
+        Some(header) if header[..4] == 3u32.to_le_bytes() => match header[12] {
+            0 => Ok(None),
+            1 => Ok(Some(Pubkey::new_from_array(header[13..45].try_into().unwrap()))),
+            _ => Err(ColabioError::InvalidProgramData.into()),
+        },
+        _ => Err(ColabioError::InvalidProgramData.into()),
+    }
+}
+
+// Escrow vault helpers
+
+/// Checks that `vault_account` is the vault PDA recorded for the project
+fn check_vault(
+    program_id: &Pubkey,
+    project_key: &Pubkey,
+    project_data: &Project,
+    vault_account: &AccountInfo,
+) -> ProgramResult {
+    let vault_key = Pubkey::create_program_address(
+        &[VAULT_SEED, project_key.as_ref(), &[project_data.vault_bump]],
+        program_id,
+    )?;
+    if vault_key != *vault_account.key {
+        return Err(ColabioError::InvalidVault.into());
+    }
+    Ok(())
+}
+
+/// Moves lamports out of a project's vault, signing with the vault seeds
+fn transfer_from_vault<'a>(
+    project_key: &Pubkey,
+    project_data: &Project,
+    vault_account: &AccountInfo<'a>,
+    recipient_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let vault_seeds: &[&[u8]] = &[VAULT_SEED, project_key.as_ref(), &[project_data.vault_bump]];
+    
+    invoke_signed(
+        &system_instruction::transfer(
+            vault_account.key,
+            recipient_account.key,
+            amount,
+        ),
+        &[vault_account.clone(), recipient_account.clone(), system_program.clone()],
+        &[vault_seeds],
+    )
+}
+
+/// Token accounts used when a project escrows an SPL mint instead of SOL
+#[derive(Clone, Copy)]
+struct TokenEscrow<'a, 'b> {
+    mint: &'b AccountInfo<'a>,
+    vault_token: &'b AccountInfo<'a>,
+    token_program: &'b AccountInfo<'a>,
+    decimals: u8,
+}
+
+/// Reads and verifies the mint, escrow token account and token program that
+/// token projects append to their account lists; returns `None` for SOL projects
+fn next_token_escrow<'a, 'b, I: Iterator<Item = &'b AccountInfo<'a>>>(
+    accounts_iter: &mut I,
+    program_id: &Pubkey,
+    project_key: &Pubkey,
+    project_data: &Project,
+) -> Result<Option<TokenEscrow<'a, 'b>>, ProgramError> {
+    let project_mint = match project_data.mint {
+        Some(mint) => mint,
+        None => return Ok(None),
+    };
+    
+    let mint = next_account_info(accounts_iter)?;
+    let vault_token = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    
+    check_spl_token_program_account(token_program.key)
+        .map_err(|_| ColabioError::InvalidTokenProgram)?;
+    if *mint.key != project_mint || mint.owner != token_program.key {
+        return Err(ColabioError::InvalidMint.into());
+    }
+    let vault_token_key = Pubkey::create_program_address(
+        &[VAULT_TOKEN_SEED, project_key.as_ref(), &[project_data.vault_token_bump]],
+        program_id,
+    )?;
+    if vault_token_key != *vault_token.key {
+        return Err(ColabioError::InvalidVault.into());
+    }
+    
+    let decimals = StateWithExtensions::<Mint>::unpack(&mint.data.borrow())?.base.decimals;
+    Ok(Some(TokenEscrow {
+        mint,
+        vault_token,
+        token_program,
+        decimals,
+    }))
+}
+
+/// Checks that a token account holds the escrow mint and belongs to `owner`
+fn check_token_account(
+    token_account: &AccountInfo,
+    escrow: &TokenEscrow,
+    owner: &Pubkey,
+) -> ProgramResult {
+    if token_account.owner != escrow.token_program.key {
+        return Err(ColabioError::InvalidTokenAccount.into());
+    }
+    let data = token_account.data.borrow();
+    let account = StateWithExtensions::<TokenAccount>::unpack(&data)?.base;
+    if account.mint != *escrow.mint.key || account.owner != *owner {
+        return Err(ColabioError::InvalidTokenAccount.into());
+    }
+    Ok(())
+}
+
+/// Reads the token balance of a token account
+fn token_balance(token_account: &AccountInfo) -> Result<u64, ProgramError> {
+    let data = token_account.data.borrow();
+    Ok(StateWithExtensions::<TokenAccount>::unpack(&data)?.base.amount)
+}
+
+/// Checks the escrow still holds at least what the project ledger says it
+/// should, on top of the vault's rent reserve for SOL projects
+fn check_escrow_balance(
+    project_data: &Project,
+    vault_account: &AccountInfo,
+    token_escrow: Option<&TokenEscrow>,
+) -> ProgramResult {
+    let held = project_data.ledger.held()?;
+    let sufficient = match token_escrow {
+        Some(escrow) => token_balance(escrow.vault_token)? >= held,
+        None => {
+            let reserve = Rent::get()?.minimum_balance(0);
+            vault_account.lamports() >= held.checked_add(reserve).ok_or(ColabioError::ArithmeticOverflow)?
+        }
+    };
+    if !sufficient {
+        return Err(ColabioError::LedgerInvariantViolated.into());
+    }
+    Ok(())
+}
+
+/// Moves funds into a project's escrow, as lamports or tokens, and returns
+/// the amount the escrow actually received after any transfer fee
+fn deposit_to_escrow<'a>(
+    vault_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    token_escrow: Option<&TokenEscrow<'a, '_>>,
+    depositor_account: &AccountInfo<'a>,
+    source_account: &AccountInfo<'a>,
+    amount: u64,
+) -> Result<u64, ProgramError> {
+    match token_escrow {
+        Some(escrow) => {
+            let balance_before = token_balance(escrow.vault_token)?;
+            invoke(
+                &spl_token_2022::instruction::transfer_checked(
+                    escrow.token_program.key,
+                    source_account.key,
+                    escrow.mint.key,
+                    escrow.vault_token.key,
+                    depositor_account.key,
+                    &[],
+                    amount,
+                    escrow.decimals,
+                )?,
+                &[
+                    source_account.clone(),
+                    escrow.mint.clone(),
+                    escrow.vault_token.clone(),
+                    depositor_account.clone(),
+                    escrow.token_program.clone(),
+                ],
+            )?;
+            Ok(token_balance(escrow.vault_token)?
+                .checked_sub(balance_before)
+                .ok_or(ColabioError::ArithmeticOverflow)?)
+        }
+        None => {
+            invoke(
+                &system_instruction::transfer(
+                    depositor_account.key,
+                    vault_account.key,
+                    amount,
+                ),
+                &[depositor_account.clone(), vault_account.clone(), system_program.clone()],
+            )?;
+            Ok(amount)
+        }
+    }
+}
+
+/// Moves funds out of a project's escrow, as lamports or tokens, signing with
+/// the vault seeds; `recipient_account` is a wallet for SOL projects and a
+/// token account for token projects
+fn transfer_from_escrow<'a>(
+    project_key: &Pubkey,
+    project_data: &Project,
+    vault_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    token_escrow: Option<&TokenEscrow<'a, '_>>,
+    recipient_account: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let escrow = match token_escrow {
+        Some(escrow) => escrow,
+        None => {
+            return transfer_from_vault(
+                project_key,
+                project_data,
+                vault_account,
+                recipient_account,
+                system_program,
+                amount,
+            )
+        }
+    };
+    
+    let vault_seeds: &[&[u8]] = &[VAULT_SEED, project_key.as_ref(), &[project_data.vault_bump]];
+    invoke_signed(
+        &spl_token_2022::instruction::transfer_checked(
+            escrow.token_program.key,
+            escrow.vault_token.key,
+            escrow.mint.key,
+            recipient_account.key,
+            vault_account.key,
+            &[],
+            amount,
+            escrow.decimals,
+        )?,
+        &[
+            escrow.vault_token.clone(),
+            escrow.mint.clone(),
+            recipient_account.clone(),
+            vault_account.clone(),
+            escrow.token_program.clone(),
+        ],
+        &[vault_seeds],
+    )
+}
+
+/// Creates a token account PDA for the escrow's mint with the vault as its
+/// token authority, sized for the account extensions the mint requires, such
+/// as the withheld-fee slot of Token-2022 transfer-fee mints
+fn create_escrow_token_account<'a>(
+    payer_account: &AccountInfo<'a>,
+    escrow: &TokenEscrow<'a, '_>,
+    vault_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let space = {
+        let mint_data = escrow.mint.data.borrow();
+        let mint_state = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+        let account_extensions =
+            ExtensionType::get_required_init_account_extensions(&mint_state.get_extension_types()?);
+        ExtensionType::try_calculate_account_len::<TokenAccount>(&account_extensions)?
+    };
+    
+    create_pda_account(
+        payer_account,
+        escrow.vault_token,
+        system_program,
+        escrow.token_program.key,
+        space,
+        signer_seeds,
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_account3(
+            escrow.token_program.key,
+            escrow.vault_token.key,
+            escrow.mint.key,
+            vault_account.key,
+        )?,
+        &[escrow.vault_token.clone(), escrow.mint.clone(), escrow.token_program.clone()],
+    )
+}
+
+// Creator bond helpers
+
+/// Points a token project's escrow at the bond token account instead of the
+/// escrow token account; SOL projects have no token escrow to redirect
+fn with_bond_account<'a, 'b>(
+    token_escrow: Option<&TokenEscrow<'a, 'b>>,
+    bond_account: &'b AccountInfo<'a>,
+) -> Option<TokenEscrow<'a, 'b>> {
+    token_escrow.map(|escrow| TokenEscrow {
+        vault_token: bond_account,
+        ..*escrow
+    })
+}
+
+/// Checks that `bond_account` is the bond PDA recorded for the project
+fn check_bond(
+    program_id: &Pubkey,
+    project_key: &Pubkey,
+    project_data: &Project,
+    bond_account: &AccountInfo,
+) -> ProgramResult {
+    let bond_key = Pubkey::create_program_address(
+        &[BOND_SEED, project_key.as_ref(), &[project_data.bond_bump]],
+        program_id,
+    )
+    .map_err(|_| ColabioError::InvalidBondAccount)?;
+    if bond_key != *bond_account.key {
+        return Err(ColabioError::InvalidBondAccount.into());
+    }
+    Ok(())
+}
+
+/// Accounts used to pay out of a project's creator bond
+struct BondAccounts<'a, 'b> {
+    vault: &'b AccountInfo<'a>,
+    bond: &'b AccountInfo<'a>,
+    system_program: &'b AccountInfo<'a>,
+}
+
+/// Moves funds out of a project's creator bond and records the payout; token
+/// bonds are signed for by the vault, SOL bonds by the bond PDA itself
+fn pay_from_bond<'a, 'b>(
+    project_key: &Pubkey,
+    project_data: &mut Project,
+    accounts: &BondAccounts<'a, 'b>,
+    token_escrow: Option<&TokenEscrow<'a, 'b>>,
+    recipient_account: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    match with_bond_account(token_escrow, accounts.bond) {
+        Some(bond_escrow) => transfer_from_escrow(
+            project_key,
+            project_data,
+            accounts.vault,
+            accounts.system_program,
+            Some(&bond_escrow),
+            recipient_account,
+            amount,
+        )?,
+        None => {
+            let bond_seeds: &[&[u8]] = &[BOND_SEED, project_key.as_ref(), &[project_data.bond_bump]];
+            invoke_signed(
+                &system_instruction::transfer(accounts.bond.key, recipient_account.key, amount),
+                &[accounts.bond.clone(), recipient_account.clone(), accounts.system_program.clone()],
+                &[bond_seeds],
+            )?;
+        }
+    }
+    project_data.bond_paid = project_data
+        .bond_paid
+        .checked_add(amount)
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// Accounts used to mint a contribution receipt
+struct ReceiptAccounts<'a, 'b> {
+    mint: &'b AccountInfo<'a>,
+    token_account: &'b AccountInfo<'a>,
+    token_program: &'b AccountInfo<'a>,
+    associated_token_program: &'b AccountInfo<'a>,
+}
+
+/// Creates a contribution's receipt mint with metadata for the project,
+/// amount and timestamp, mints the single token to the contributor and
+/// revokes the mint authority
+fn mint_contribution_receipt<'a>(
+    contribution: &Contribution,
+    vault_account: &AccountInfo<'a>,
+    vault_seeds: &[&[u8]],
+    contributor_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    receipt: &ReceiptAccounts<'a, '_>,
+    receipt_seeds: &[&[u8]],
+) -> ProgramResult {
+    let token_program_id = receipt.token_program.key;
+    if *token_program_id != spl_token_2022::id()
+        || *receipt.associated_token_program.key != spl_associated_token_account::id()
+    {
+        return Err(ColabioError::InvalidTokenProgram.into());
+    }
+    
+    // The metadata is built up front so the mint can be funded for its final size
+    let metadata = TokenMetadata {
+        name: RECEIPT_NAME.to_string(),
+        symbol: RECEIPT_SYMBOL.to_string(),
+        additional_metadata: vec![
+            ("project".to_string(), contribution.project.to_string()),
+            ("amount".to_string(), contribution.amount.to_string()),
+            ("timestamp".to_string(), contribution.timestamp.to_string()),
+        ],
+        ..Default::default()
+    };
+    
+    // Create the mint with room for the metadata pointer; Token-2022 grows the
+    // account itself when metadata is written, but the rent must already be there
+    let mint_space = ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::MetadataPointer])?;
+    create_pda_account(
+        contributor_account,
+        receipt.mint,
+        system_program,
+        token_program_id,
+        mint_space,
+        receipt_seeds,
+    )?;
+    let required_lamports = Rent::get()?.minimum_balance(mint_space + metadata.tlv_size_of()?);
+    if receipt.mint.lamports() < required_lamports {
+        invoke(
+            &system_instruction::transfer(
+                contributor_account.key,
+                receipt.mint.key,
+                required_lamports - receipt.mint.lamports(),
+            ),
+            &[contributor_account.clone(), receipt.mint.clone(), system_program.clone()],
+        )?;
+    }
+    
+    // Point the mint's metadata at itself and initialize it as a 0-decimal mint
+    invoke(
+        &metadata_pointer::instruction::initialize(
+            token_program_id,
+            receipt.mint.key,
+            Some(*vault_account.key),
+            Some(*receipt.mint.key),
+        )?,
+        &[receipt.mint.clone(), receipt.token_program.clone()],
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_mint2(
+            token_program_id,
+            receipt.mint.key,
+            vault_account.key,
+            None,
+            0,
+        )?,
+        &[receipt.mint.clone(), receipt.token_program.clone()],
+    )?;
+    
+    // Write the metadata, signing as mint and update authority
+    invoke_signed(
+        &spl_token_metadata_interface::instruction::initialize(
+            token_program_id,
+            receipt.mint.key,
+            vault_account.key,
+            receipt.mint.key,
+            vault_account.key,
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        ),
+        &[receipt.mint.clone(), vault_account.clone(), receipt.token_program.clone()],
+        &[vault_seeds],
+    )?;
+    for (key, value) in metadata.additional_metadata {
+        invoke_signed(
+            &spl_token_metadata_interface::instruction::update_field(
+                token_program_id,
+                receipt.mint.key,
+                vault_account.key,
+                Field::Key(key),
+                value,
+            ),
+            &[receipt.mint.clone(), vault_account.clone(), receipt.token_program.clone()],
+            &[vault_seeds],
+        )?;
+    }
+    
+    // Mint the single receipt token to the contributor, then fix the supply
+    invoke(
+        &spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            contributor_account.key,
+            contributor_account.key,
+            receipt.mint.key,
+            token_program_id,
+        ),
+        &[
+            contributor_account.clone(),
+            receipt.token_account.clone(),
+            receipt.mint.clone(),
+            system_program.clone(),
+            receipt.token_program.clone(),
+            receipt.associated_token_program.clone(),
+        ],
+    )?;
+    invoke_signed(
+        &spl_token_2022::instruction::mint_to(
+            token_program_id,
+            receipt.mint.key,
+            receipt.token_account.key,
+            vault_account.key,
+            &[],
+            1,
+        )?,
+        &[
+            receipt.mint.clone(),
+            receipt.token_account.clone(),
+            vault_account.clone(),
+            receipt.token_program.clone(),
+        ],
+        &[vault_seeds],
+    )?;
+    invoke_signed(
+        &spl_token_2022::instruction::set_authority(
+            token_program_id,
+            receipt.mint.key,
+            None,
+            AuthorityType::MintTokens,
+            vault_account.key,
+            &[],
+        )?,
+        &[receipt.mint.clone(), vault_account.clone(), receipt.token_program.clone()],
+        &[vault_seeds],
+    )
+}
+
+/// Checks that `signer` controls a contribution: the original contributor,
+/// or for contributions with a receipt, whoever holds the receipt token
+fn check_contribution_authority(
+    signer: &Pubkey,
+    contribution: &Contribution,
+    receipt_token_account: Option<&AccountInfo>,
+) -> ProgramResult {
+    let (receipt_mint, receipt_token_account) = match (contribution.receipt_mint, receipt_token_account) {
+        (None, _) => {
+            if contribution.contributor != *signer {
+                return Err(ColabioError::NotContributionHolder.into());
+            }
+            return Ok(());
+        }
+        (Some(receipt_mint), Some(receipt_token_account)) => (receipt_mint, receipt_token_account),
+        (Some(_), None) => return Err(ProgramError::NotEnoughAccountKeys),
+    };
+    
+    if *receipt_token_account.owner != spl_token_2022::id() {
+        return Err(ColabioError::NotContributionHolder.into());
+    }
+    let data = receipt_token_account.data.borrow();
+    let account = StateWithExtensions::<TokenAccount>::unpack(&data)?.base;
+    if account.mint != receipt_mint || account.owner != *signer || account.amount != 1 {
+        return Err(ColabioError::NotContributionHolder.into());
+    }
+    Ok(())
+}
+
+/// Creates a PDA owned by `owner`, tolerating lamports already sent to the address
+fn create_pda_account<'a>(
+    payer_account: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    owner: &Pubkey,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let rent = Rent::get()?;
+    let required_lamports = rent.minimum_balance(space);
+    
+    if new_account.lamports() == 0 {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer_account.key,
+                new_account.key,
+                required_lamports,
+                space as u64,
+                owner,
+            ),
+            &[payer_account.clone(), new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )
+    } else {
+        // Someone pre-funded the address, so create_account would fail; top
+        // up, allocate and assign instead
+        let top_up = required_lamports.saturating_sub(new_account.lamports());
+        if top_up > 0 {
+            invoke(
+                &system_instruction::transfer(payer_account.key, new_account.key, top_up),
+                &[payer_account.clone(), new_account.clone(), system_program.clone()],
+            )?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(new_account.key, space as u64),
+            &[new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(new_account.key, owner),
+            &[new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )
+    }
+}
+
+// Account sizing helpers
+
+/// Grows an account to `new_len` bytes, topping up rent from the payer
+fn resize_account<'a>(
+    account: &AccountInfo<'a>,
+    payer_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    new_len: usize,
+) -> ProgramResult {
+    let rent = Rent::get()?;
+    let required_lamports = rent.minimum_balance(new_len);
+    
+    if account.lamports() < required_lamports {
+        invoke(
+            &system_instruction::transfer(
+                payer_account.key,
+                account.key,
+                required_lamports - account.lamports(),
+            ),
+            &[payer_account.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    
+    account.realloc(new_len, false)
+}
+
+/// Serializes a project into its account, growing the account if needed
+fn write_project<'a>(
+    project_account: &AccountInfo<'a>,
+    project_data: &Project,
+    payer_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let data = project_data.try_to_vec()?;
+    if data.len() > MAX_PROJECT_SPACE {
+        return Err(ColabioError::ProjectTooLarge.into());
+    }
+    
+    project_data.check_invariants()?;
+    
+    if data.len() > project_account.data_len() {
+        resize_account(project_account, payer_account, system_program, data.len())?;
+    }
+    
+    project_account.data.borrow_mut()[..data.len()].copy_from_slice(&data);
+    Ok(())
+}
+
+/// Writes a project back to its account after checking its ledger invariants
+fn save_project(project_account: &AccountInfo, project_data: &Project) -> ProgramResult {
+    project_data.check_invariants()?;
+    project_data.serialize(&mut *project_account.data.borrow_mut())?;
+    Ok(())
+}
+
+/// Reads the cluster clock, refusing timestamps before the Unix epoch
+fn current_timestamp() -> Result<u64, ProgramError> {
+    let clock = solana_program::clock::Clock::get()?;
+    Ok(u64::try_from(clock.unix_timestamp).map_err(|_| ColabioError::InvalidClock)?)
+}
+
+/// Moves lamports between accounts when the source is owned by this program
+fn move_lamports(source: &AccountInfo, destination: &AccountInfo, amount: u64) -> ProgramResult {
+    let source_lamports = source.lamports().checked_sub(amount).ok_or(ColabioError::ArithmeticOverflow)?;
+    let destination_lamports = destination
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    **source.lamports.borrow_mut() = source_lamports;
+    **destination.lamports.borrow_mut() = destination_lamports;
+    Ok(())
+}
+
+// Process functions for each instruction
+fn process_initialize_project(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: ProjectArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let creator_account = next_account_info(accounts_iter)?;
+    let project_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let rent_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    
+    // Verify creator is signer
+    if !creator_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Check the project definition against the creation limits
+    let config = Config::load_checked(config_account, program_id)?;
+    args.validate(&config.params)?;
+    
+    // Verify the vault is the canonical PDA for this project
+    let (vault_key, vault_bump) = find_vault_address(program_id, project_account.key);
+    if vault_key != *vault_account.key {
+        return Err(ColabioError::InvalidVault.into());
+    }
+    
+    let rent = Rent::from_account_info(rent_account)?;
+    
+    // Fund the vault up to the rent-exempt minimum so deposits of any size land
+    let vault_minimum = rent.minimum_balance(0);
+    if vault_account.lamports() < vault_minimum {
+        invoke(
+            &system_instruction::transfer(
+                creator_account.key,
+                vault_account.key,
+                vault_minimum - vault_account.lamports(),
+            ),
+            &[creator_account.clone(), vault_account.clone(), system_program.clone()],
+        )?;
+    }
+    
+    // Create the escrow token account for projects that accept an SPL mint
+    let (_, vault_token_bump) = find_vault_token_address(program_id, project_account.key);
+    let token_escrow = match args.mint {
+        Some(project_mint) => {
+            let mint_account = next_account_info(accounts_iter)?;
+            let vault_token_account = next_account_info(accounts_iter)?;
+            let token_program = next_account_info(accounts_iter)?;
+            
+            check_spl_token_program_account(token_program.key)
+                .map_err(|_| ColabioError::InvalidTokenProgram)?;
+            if *mint_account.key != project_mint || mint_account.owner != token_program.key {
+                return Err(ColabioError::InvalidMint.into());
+            }
+            
+            let escrow = TokenEscrow {
+                mint: mint_account,
+                vault_token: vault_token_account,
+                token_program,
+                decimals: StateWithExtensions::<Mint>::unpack(&mint_account.data.borrow())?.base.decimals,
+            };
+            create_escrow_token_account(
+                creator_account,
+                &escrow,
+                vault_account,
+                system_program,
+                &[VAULT_TOKEN_SEED, project_account.key.as_ref(), &[vault_token_bump]],
+            )?;
+            Some(escrow)
+        }
+        None => None,
+    };
+    
+    // Set up the creator bond PDA in the project's currency
+    let bond_account = next_account_info(accounts_iter)?;
+    let (bond_key, bond_bump) = find_bond_address(program_id, project_account.key);
+    if bond_key != *bond_account.key {
+        return Err(ColabioError::InvalidBondAccount.into());
+    }
+    let bond_seeds: &[&[u8]] = &[BOND_SEED, project_account.key.as_ref(), &[bond_bump]];
+    let bond_escrow = with_bond_account(token_escrow.as_ref(), bond_account);
+    let bond_source = match &bond_escrow {
+        Some(escrow) => {
+            create_escrow_token_account(creator_account, escrow, vault_account, system_program, bond_seeds)?;
+            let creator_token_account = next_account_info(accounts_iter)?;
+            check_token_account(creator_token_account, escrow, creator_account.key)?;
+            creator_token_account
+        }
+        None => {
+            // Like the vault, the bond holds a rent reserve so any bond lands
+            if bond_account.lamports() < vault_minimum {
+                invoke(
+                    &system_instruction::transfer(
+                        creator_account.key,
+                        bond_account.key,
+                        vault_minimum - bond_account.lamports(),
+                    ),
+                    &[creator_account.clone(), bond_account.clone(), system_program.clone()],
+                )?;
+            }
+            creator_account
+        }
+    };
+    
+    // Deposit the bond, recording what actually arrived after transfer fees
+    let bond_amount = config.params.creator_bond(args.goal_amount);
+    let creator_bond = if bond_amount > 0 {
+        deposit_to_escrow(
+            bond_account,
+            system_program,
+            bond_escrow.as_ref(),
+            creator_account,
+            bond_source,
+            bond_amount,
+        )?
+    } else {
+        0
+    };
+    
+    // Get current timestamp
+    let current_time = current_timestamp()?;
+    
+    // Approval voting runs first; the funding period follows it
+    let voting_end = current_time
+        .checked_add(config.params.voting_period)
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    let end_time = voting_end.checked_add(args.duration).ok_or(ColabioError::ArithmeticOverflow)?;
+    
+    // Create project data
+    let project_data = Project {
+        account_type: AccountType::Project,
+        version: Project::VERSION,
+        creator: *creator_account.key,
+        title: args.title,
+        description: args.description,
+        goal_amount: args.goal_amount,
+        ledger: EscrowLedger::default(),
+        start_time: voting_end,
+        end_time,
+        milestones: args.milestones.into_iter().map(Milestone::from).collect(),
+        status: ProjectStatus::Pending,
+        approve_votes: 0,
+        reject_votes: 0,
+        vault_bump,
+        funding_mode: args.funding_mode,
+        mint: args.mint,
+        vault_token_bump,
+        voting_start: current_time,
+        voting_end,
+        remainder_policy: args.remainder_policy,
+        sequential_milestones: args.sequential_milestones,
+        activated_at: 0,
+        bond_bump,
+        creator_bond,
+        bond_paid: 0,
+    };
+    
+    // Size the project account from the serialized project
+    let space = project_data.try_to_vec()?.len();
+    if space > MAX_PROJECT_SPACE {
+        return Err(ColabioError::ProjectTooLarge.into());
+    }
+    
+    // Create the project account if the program does not own it yet
+    check_uninitialized(project_account, program_id)?;
+    if project_account.owner != program_id {
+        let lamports = rent.minimum_balance(space);
+        
+        invoke(
+            &system_instruction::create_account(
+                creator_account.key,
+                project_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            ),
+            &[creator_account.clone(), project_account.clone(), system_program.clone()],
+        )?;
+    }
+    
+    // Serialize and save project data
+    write_project(project_account, &project_data, creator_account, system_program)?;
+    
+    msg!("Project initialized: {}", project_data.title);
+    Ok(())
+}
+
+fn process_contribute(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    mint_receipt: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let contributor_account = next_account_info(accounts_iter)?;
+    let project_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let contribution_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    
+    // Verify contributor is signer
+    if !contributor_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Load project data, verifying owner, discriminator and version
+    let mut project_data = Project::load(project_account, program_id)?;
+    
+    // Verify the vault belongs to this project
+    check_vault(program_id, project_account.key, &project_data, vault_account)?;
+    
+    // Check project status
+    if project_data.status != ProjectStatus::Active {
+        return Err(ColabioError::ProjectNotActive.into());
+    }
+    
+    // Check if project is still accepting contributions
+    let current_time = current_timestamp()?;
+    
+    if current_time > project_data.end_time {
+        return Err(ColabioError::FundingPeriodEnded.into());
+    }
+    
+    // Create contribution account if needed
+    check_uninitialized(contribution_account, program_id)?;
+    if contribution_account.owner != program_id {
+        let rent = Rent::get()?;
+        let space = Contribution::LEN;
+        let lamports = rent.minimum_balance(space);
+        
+        invoke(
+            &system_instruction::create_account(
+                contributor_account.key,
+                contribution_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            ),
+            &[contributor_account.clone(), contribution_account.clone(), system_program.clone()],
+        )?;
+    }
+    
+    // Transfer SOL or tokens from contributor into the project escrow
+    let token_escrow = next_token_escrow(accounts_iter, program_id, project_account.key, &project_data)?;
+    let source_account = match token_escrow {
+        Some(_) => next_account_info(accounts_iter)?,
+        None => contributor_account,
+    };
+    let received = deposit_to_escrow(
+        vault_account,
+        system_program,
+        token_escrow.as_ref(),
+        contributor_account,
+        source_account,
+        amount,
+    )?;
+    
+    // Record what the escrow actually received
+    project_data.ledger.deposit(received)?;
+    check_escrow_balance(&project_data, vault_account, token_escrow.as_ref())?;
+    save_project(project_account, &project_data)?;
+    
+    // Create contribution record
+    let mut contribution_data = Contribution {
+        account_type: AccountType::Contribution,
+        version: Contribution::VERSION,
+        contributor: *contributor_account.key,
+        project: *project_account.key,
+        amount: received,
+        timestamp: current_time,
+        refunded: false,
+        mint: project_data.mint,
+        receipt_mint: None,
+    };
+    
+    // Mint a receipt token that carries the contribution's rights
+    if mint_receipt {
+        let receipt = ReceiptAccounts {
+            mint: next_account_info(accounts_iter)?,
+            token_account: next_account_info(accounts_iter)?,
+            token_program: next_account_info(accounts_iter)?,
+            associated_token_program: next_account_info(accounts_iter)?,
+        };
+        
+        let (receipt_mint_key, receipt_bump) = find_receipt_mint_address(program_id, contribution_account.key);
+        if receipt_mint_key != *receipt.mint.key {
+            return Err(ColabioError::InvalidReceiptMint.into());
+        }
+        
+        mint_contribution_receipt(
+            &contribution_data,
+            vault_account,
+            &[VAULT_SEED, project_account.key.as_ref(), &[project_data.vault_bump]],
+            contributor_account,
+            system_program,
+            &receipt,
+            &[RECEIPT_SEED, contribution_account.key.as_ref(), &[receipt_bump]],
+        )?;
+        contribution_data.receipt_mint = Some(receipt_mint_key);
+    }
+    
+    // Serialize and save contribution data
+    contribution_data.serialize(&mut *contribution_account.data.borrow_mut())?;
+    
+    msg!("Contribution of {} received", received);
+    Ok(())
+}
+
+fn process_validate_milestone(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    milestone_index: u8,
+    evidence_hash: [u8; 32],
+    approve: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let validator_account = next_account_info(accounts_iter)?;
+    let project_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let validation_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let validator_stake_account = next_account_info(accounts_iter)?;
+    
+    // Verify validator is signer
+    if !validator_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Load project data, verifying owner, discriminator and version
+    let mut project_data = Project::load(project_account, program_id)?;
+    let config = Config::load_checked(config_account, program_id)?;
+    
+    // Verify the validator is registered, active and sufficiently staked
+    if validator_stake_account.owner != program_id {
+        return Err(ColabioError::ValidatorNotRegistered.into());
+    }
+    let validator_stake =
+        ValidatorStake::load_checked(validator_stake_account, program_id, validator_account.key)?;
+    if validator_stake.unbonding_end != 0 {
+        return Err(ColabioError::ValidatorUnbonding.into());
+    }
+    if validator_stake.stake < config.params.min_validator_stake {
+        return Err(ColabioError::InsufficientValidatorStake.into());
+    }
+    
+    // Creators cannot validate their own milestones
+    if project_data.creator == *validator_account.key {
+        return Err(ColabioError::CreatorCannotValidate.into());
+    }
+    
+    // Check project status
+    if project_data.status != ProjectStatus::Active && project_data.status != ProjectStatus::Funded {
+        return Err(ColabioError::ProjectNotActive.into());
+    }
+    
+    // Check milestone index
+    if milestone_index as usize >= project_data.milestones.len() {
+        return Err(ColabioError::InvalidMilestoneIndex.into());
+    }
+    
+    // Check the milestone is still awaiting validation
+    if project_data.milestones[milestone_index as usize].status != MilestoneStatus::Pending {
+        return Err(ColabioError::MilestoneNotPending.into());
+    }
+    
+    // Check the milestone's prerequisites have been released
+    if !project_data.prerequisites_released(milestone_index as usize) {
+        return Err(ColabioError::PrerequisitesNotReleased.into());
+    }
+    
+    // Check the validator reviewed the milestone's latest evidence
+    match project_data.milestones[milestone_index as usize].evidence.last() {
+        None => return Err(ColabioError::MissingEvidence.into()),
+        Some(evidence) if evidence.hash != evidence_hash => {
+            return Err(ColabioError::EvidenceMismatch.into());
+        }
+        Some(_) => {}
+    }
+    
+    // Verify the validation account is this validator's record PDA for the
+    // milestone's current round
+    let round = project_data.milestones[milestone_index as usize].round;
+    let (validation_key, validation_bump) = find_validation_address(
+        program_id,
+        project_account.key,
+        milestone_index,
+        round,
+        validator_account.key,
+    );
+    if validation_key != *validation_account.key {
+        return Err(ColabioError::InvalidValidationAccount.into());
+    }
+    
+    // Reject repeat validations of the same milestone
+    if validation_account.owner == program_id {
+        return Err(ColabioError::AlreadyValidated.into());
+    }
+    
+    // Create the validation record
+    let space = Validation::LEN;
+    create_pda_account(
+        validator_account,
+        validation_account,
+        system_program,
+        program_id,
+        space,
+        &[
+            VALIDATION_SEED,
+            project_account.key.as_ref(),
+            &[milestone_index],
+            &round.to_le_bytes(),
+            validator_account.key.as_ref(),
+            &[validation_bump],
+        ],
+    )?;
+    
+    // Update milestone validations
+    let current_time = current_timestamp()?;
+    let milestone = &mut project_data.milestones[milestone_index as usize];
+    if approve {
+        milestone.validations = milestone.validations
+            .checked_add(1)
+            .ok_or(ColabioError::ArithmeticOverflow)?;
+        
+        // Check if milestone has passed both the validator and funder thresholds
+        milestone.refresh_status(&config.params, project_data.ledger.total_deposited, current_time);
+    } else {
+        milestone.rejections = milestone.rejections
+            .checked_add(1)
+            .ok_or(ColabioError::ArithmeticOverflow)?;
+        
+        // Reject the milestone once enough validators have rejected it
+        if milestone.rejections >= config.params.rejection_threshold {
+            milestone.status = MilestoneStatus::Rejected;
+            
+            // Without resubmissions left the project is forced into refund
+            if milestone.resubmissions >= config.params.max_resubmissions {
+                project_data.status = ProjectStatus::Cancelled;
+                msg!("Milestone {} rejected with no resubmissions left, project cancelled", milestone_index);
+            }
+        }
+    }
+    
+    // Save updated project data
+    save_project(project_account, &project_data)?;
+    
+    // Create validation record
+    let validation_data = Validation {
+        account_type: AccountType::Validation,
+        version: Validation::VERSION,
+        validator: *validator_account.key,
+        project: *project_account.key,
+        milestone_index,
+        round,
+        evidence_hash,
+        approve,
+        timestamp: current_time,
+        slashed: false,
+        bump: validation_bump,
+    };
+    
+    // Serialize and save validation data
+    validation_data.serialize(&mut *validation_account.data.borrow_mut())?;
+    
+    msg!("Milestone {} {}", milestone_index, if approve { "validated" } else { "rejected" });
+    Ok(())
+}
+
+fn process_release_funds(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    milestone_index: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let creator_account = next_account_info(accounts_iter)?;
+    let project_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let fee_recipient_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    
+    // Verify creator is signer
+    if !creator_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Load project data, verifying owner, discriminator and version
+    let mut project_data = Project::load(project_account, program_id)?;
+    
+    // Verify creator is project owner
+    if project_data.creator != *creator_account.key {
+        return Err(ColabioError::NotProjectCreator.into());
+    }
+    
+    // Verify the fee recipient matches the Config
+    let config = Config::load_checked(config_account, program_id)?;
+    if config.params.fee_recipient != *fee_recipient_account.key {
+        return Err(ColabioError::InvalidFeeRecipient.into());
+    }
+    
+    // Verify the vault belongs to this project
+    check_vault(program_id, project_account.key, &project_data, vault_account)?;
+    
+    // Check the project is allowed to pay out
+    if !project_data.can_release_funds() {
+        if project_data.status == ProjectStatus::Active {
+            return Err(ColabioError::FundingNotFinalized.into());
+        }
+        return Err(ColabioError::ProjectNotActive.into());
+    }
+    
+    // Check milestone index
+    if milestone_index as usize >= project_data.milestones.len() {
+        return Err(ColabioError::InvalidMilestoneIndex.into());
+    }
+    
+    // Check the milestone is validated and has not been paid out already
+    match project_data.milestones[milestone_index as usize].status {
+        MilestoneStatus::Validated => {}
+        MilestoneStatus::Released => return Err(ColabioError::MilestoneAlreadyReleased.into()),
+        _ => return Err(ColabioError::MilestoneNotValidated.into()),
+    }
+    
+    // Check the milestone's prerequisites have been released
+    if !project_data.prerequisites_released(milestone_index as usize) {
+        return Err(ColabioError::PrerequisitesNotReleased.into());
+    }
+    
+    // Hold the release while a dispute is open or the challenge window runs
+    let current_time = current_timestamp()?;
+    let milestone = &project_data.milestones[milestone_index as usize];
+    
+    if milestone.disputed {
+        return Err(ColabioError::MilestoneDisputed.into());
+    }
+    let challenge_end = milestone
+        .validated_at
+        .checked_add(config.params.challenge_period)
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    if current_time <= challenge_end {
+        return Err(ColabioError::ChallengePeriodActive.into());
+    }
+    
+    // Get milestone amount and split off the platform fee
+    let amount = project_data.milestones[milestone_index as usize].amount;
+    if amount > project_data.ledger.held()? {
+        return Err(ColabioError::InsufficientEscrow.into());
+    }
+    let fee = (amount as u128 * config.params.fee_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+    let creator_amount = amount.checked_sub(fee).ok_or(ColabioError::ArithmeticOverflow)?;
+    
+    // Resolve payout destinations: wallets for SOL, token accounts for tokens
+    let token_escrow = next_token_escrow(accounts_iter, program_id, project_account.key, &project_data)?;
+    let (creator_destination, fee_destination) = match &token_escrow {
+        Some(escrow) => {
+            let creator_token_account = next_account_info(accounts_iter)?;
+            let fee_token_account = next_account_info(accounts_iter)?;
+            check_token_account(creator_token_account, escrow, creator_account.key)?;
+            check_token_account(fee_token_account, escrow, fee_recipient_account.key)?;
+            (creator_token_account, fee_token_account)
+        }
+        None => (creator_account, fee_recipient_account),
+    };
+    
+    // Transfer funds from the project escrow to creator and fee recipient
+    transfer_from_escrow(
+        project_account.key,
+        &project_data,
+        vault_account,
+        system_program,
+        token_escrow.as_ref(),
+        creator_destination,
+        creator_amount,
+    )?;
+    if fee > 0 {
+        transfer_from_escrow(
+            project_account.key,
+            &project_data,
+            vault_account,
+            system_program,
+            token_escrow.as_ref(),
+            fee_destination,
+            fee,
+        )?;
+    }
+    project_data.ledger.release(creator_amount, fee)?;
+    
+    // Record the release so the milestone cannot be paid out again
+    let milestone = &mut project_data.milestones[milestone_index as usize];
+    milestone.status = MilestoneStatus::Released;
+    milestone.released_at = current_time;
+    milestone.released_amount = amount;
+    
+    // Check if all milestones are released
+    let all_released = project_data
+        .milestones
+        .iter()
+        .all(|m| m.status == MilestoneStatus::Released);
+    if all_released {
+        project_data.status = ProjectStatus::Completed;
+    }
+    
+    // Save updated project data
+    check_escrow_balance(&project_data, vault_account, token_escrow.as_ref())?;
+    save_project(project_account, &project_data)?;
+    
+    msg!("Released {} for milestone {}", amount, milestone_index);
+    Ok(())
+}
+
+fn process_cancel_project(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let creator_account = next_account_info(accounts_iter)?;
+    let project_account = next_account_info(accounts_iter)?;
+    
+    // Verify creator is signer
+    if !creator_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Load project data, verifying owner, discriminator and version
+    let mut project_data = Project::load(project_account, program_id)?;
+    
+    // Verify creator is project owner
+    if project_data.creator != *creator_account.key {
+        return Err(ColabioError::NotProjectCreator.into());
+    }
+    
+    // Check project status
+    if project_data.status != ProjectStatus::Pending
+        && project_data.status != ProjectStatus::Active
+        && project_data.status != ProjectStatus::Funded
+    {
+        return Err(ColabioError::ProjectNotCancellable.into());
+    }
+    
+    // Set project status to cancelled
+    project_data.status = ProjectStatus::Cancelled;
+    
+    // Save updated project data
+    save_project(project_account, &project_data)?;
+    
+    msg!("Project cancelled");
+    Ok(())
+}
+
+fn process_vote(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    approve: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let voter_account = next_account_info(accounts_iter)?;
+    let project_account = next_account_info(accounts_iter)?;
+    let vote_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    
+    // Verify voter is signer
+    if !voter_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Load project data, verifying owner, discriminator and version
+    let mut project_data = Project::load(project_account, program_id)?;
+    
+    // Check project status
+    if project_data.status != ProjectStatus::Pending {
+        return Err(ColabioError::ProjectNotPending.into());
+    }
+    
+    // Check the voting window is open
+    let current_time = current_timestamp()?;
+    
+    if current_time < project_data.voting_start || current_time > project_data.voting_end {
+        return Err(ColabioError::VotingClosed.into());
+    }
+    
+    // Verify the vote account is this voter's vote record PDA
+    let (vote_key, vote_bump) = find_vote_address(program_id, project_account.key, voter_account.key);
+    if vote_key != *vote_account.key {
+        return Err(ColabioError::InvalidVoteAccount.into());
+    }
+    
+    // Refuse to vote again if the record already exists
+    if vote_account.owner == program_id {
+        let existing_vote = Vote::load(vote_account, program_id)?;
+        if existing_vote.voter != *voter_account.key || existing_vote.project != *project_account.key {
+            return Err(ColabioError::VoteRecordMismatch.into());
+        }
+        return Err(ColabioError::AlreadyVoted.into());
+    }
+    
+    // Create the vote record
+    let space = Vote::LEN;
+    create_pda_account(
+        voter_account,
+        vote_account,
+        system_program,
+        program_id,
+        space,
+        &[VOTE_SEED, project_account.key.as_ref(), voter_account.key.as_ref(), &[vote_bump]],
+    )?;
+    
+    // Update project votes
+    if approve {
+        project_data.approve_votes = project_data.approve_votes
+            .checked_add(1)
+            .ok_or(ColabioError::ArithmeticOverflow)?;
+    } else {
+        project_data.reject_votes = project_data.reject_votes
+            .checked_add(1)
+            .ok_or(ColabioError::ArithmeticOverflow)?;
+    }
+    
+    // Save updated project data
+    save_project(project_account, &project_data)?;
+    
+    // Create vote record
+    let vote_data = Vote {
+        account_type: AccountType::Vote,
+        version: Vote::VERSION,
+        voter: *voter_account.key,
+        project: *project_account.key,
+        approve,
+        timestamp: current_time,
+        bump: vote_bump,
+    };
+    
+    // Serialize and save vote data
+    vote_data.serialize(&mut *vote_account.data.borrow_mut())?;
+    
+    msg!("Vote recorded: {}", if approve { "approve" } else { "reject" });
+    Ok(())
+}
+
+fn process_claim_refund(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let contributor_account = next_account_info(accounts_iter)?;
+    let project_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let contribution_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    
+    // Verify contributor is signer
+    if !contributor_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Load project and contribution data, verifying owner, discriminator and version
+    let mut project_data = Project::load(project_account, program_id)?;
+    let mut contribution_data = Contribution::load(contribution_account, program_id)?;
+    
+    // Verify the contribution belongs to this project
+    if contribution_data.project != *project_account.key {
+        return Err(ColabioError::ContributionMismatch.into());
+    }
+    
+    // Verify the signer made the contribution or holds its receipt
+    let receipt_token_account = match contribution_data.receipt_mint {
+        Some(_) => Some(next_account_info(accounts_iter)?),
+        None => None,
+    };
+    check_contribution_authority(contributor_account.key, &contribution_data, receipt_token_account)?;
+    
+    // Check the contribution has not been refunded already
+    if contribution_data.refunded {
+        return Err(ColabioError::AlreadyRefunded.into());
+    }
+    
+    // Verify the vault belongs to this project
+    check_vault(program_id, project_account.key, &project_data, vault_account)?;
+    
+    // Check the project is refundable
+    if !project_data.is_refundable() {
+        return Err(ColabioError::ProjectNotRefundable.into());
+    }
+    
+    // Refund the contributor's share of whatever has not been released
+    let unreleased = project_data.ledger.unreleased()?;
+    let refund = project_data.ledger.pro_rata_share(contribution_data.amount, unreleased);
+    
+    let token_escrow = next_token_escrow(accounts_iter, program_id, project_account.key, &project_data)?;
+    let refund_destination = match &token_escrow {
+        Some(escrow) => {
+            let contributor_token_account = next_account_info(accounts_iter)?;
+            check_token_account(contributor_token_account, escrow, contributor_account.key)?;
+            contributor_token_account
+        }
+        None => contributor_account,
+    };
+    transfer_from_escrow(
+        project_account.key,
+        &project_data,
+        vault_account,
+        system_program,
+        token_escrow.as_ref(),
+        refund_destination,
+        refund,
+    )?;
+    
+    // Pay out the contributor's share of a forfeited creator bond
+    let bond_account = next_account_info(accounts_iter)?;
+    check_bond(program_id, project_account.key, &project_data, bond_account)?;
+    if project_data.bond_forfeited() {
+        let bond_share = project_data
+            .ledger
+            .pro_rata_share(contribution_data.amount, project_data.creator_bond);
+        if bond_share > 0 {
+            let bond_accounts = BondAccounts {
+                vault: vault_account,
+                bond: bond_account,
+                system_program,
+            };
+            pay_from_bond(
+                project_account.key,
+                &mut project_data,
+                &bond_accounts,
+                token_escrow.as_ref(),
+                refund_destination,
+                bond_share,
+            )?;
+        }
+    }
+    
+    // Mark the contribution refunded so it cannot be claimed twice
+    contribution_data.refunded = true;
+    contribution_data.serialize(&mut *contribution_account.data.borrow_mut())?;
+    
+    // Save updated project data
+    project_data.ledger.refund(refund)?;
+    check_escrow_balance(&project_data, vault_account, token_escrow.as_ref())?;
+    save_project(project_account, &project_data)?;
+    
+    msg!("Refunded {}", refund);
+    Ok(())
+}
+
+fn process_add_milestone(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    milestone: MilestoneArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let creator_account = next_account_info(accounts_iter)?;
+    let project_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let bond_account = next_account_info(accounts_iter)?;
+    
+    // Verify creator is signer
+    if !creator_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Load project and config data
+    let mut project_data = Project::load(project_account, program_id)?;
+    let config = Config::load_checked(config_account, program_id)?;
+    
+    // Verify creator is project owner
+    if project_data.creator != *creator_account.key {
+        return Err(ColabioError::NotProjectCreator.into());
+    }
+    
+    // Verify the bond belongs to this project
+    check_bond(program_id, project_account.key, &project_data, bond_account)?;
+    
+    // Check project status
+    if project_data.status != ProjectStatus::Pending {
+        return Err(ColabioError::ProjectNotPending.into());
+    }
+    
+    // Voters must see the project they vote on, so lock it after the first vote
+    if project_data.approve_votes > 0 || project_data.reject_votes > 0 {
+        return Err(ColabioError::ProjectLocked.into());
+    }
+    
+    // Check the milestone against the creation limits
+    let due_after: Vec<u64> = project_data.milestones.iter().map(|milestone| milestone.due_after).collect();
+    milestone.validate(&due_after, project_data.sequential_milestones)?;
+    if project_data.milestones.len() >= MAX_MILESTONES {
+        return Err(ColabioError::InvalidMilestoneCount.into());
+    }
+    
+    // Append the milestone and grow the goal to cover it
+    let previous_goal = project_data.goal_amount;
+    project_data.goal_amount = project_data
+        .goal_amount
+        .checked_add(milestone.amount)
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    project_data.milestones.push(Milestone::from(milestone));
+    
+    // Resolve the bond source: the wallet for SOL, a token account for tokens
+    let token_escrow = next_token_escrow(accounts_iter, program_id, project_account.key, &project_data)?;
+    let bond_escrow = with_bond_account(token_escrow.as_ref(), bond_account);
+    let bond_source = match &bond_escrow {
+        Some(escrow) => {
+            let creator_token_account = next_account_info(accounts_iter)?;
+            check_token_account(creator_token_account, escrow, creator_account.key)?;
+            creator_token_account
+        }
+        None => creator_account,
+    };
+    
+    // Top up the bond for the larger goal, recording what actually arrived
+    // after transfer fees
+    let bond_top_up = config.params.creator_bond(project_data.goal_amount)
+        .checked_sub(config.params.creator_bond(previous_goal))
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    if bond_top_up > 0 {
+        let received = deposit_to_escrow(
+            bond_account,
+            system_program,
+            bond_escrow.as_ref(),
+            creator_account,
+            bond_source,
+            bond_top_up,
+        )?;
+        project_data.creator_bond = project_data.creator_bond
+            .checked_add(received)
+            .ok_or(ColabioError::ArithmeticOverflow)?;
+    }
+    
+    // Save updated project data, reallocating if it no longer fits
+    write_project(project_account, &project_data, creator_account, system_program)?;
+    
+    msg!("Milestone {} added", project_data.milestones.len() - 1);
+    Ok(())
+}
+
+fn process_initialize_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: ConfigParams,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let authority_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let program_data_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    
+    // Verify authority is signer
+    if !authority_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Verify the signer is this program's upgrade authority
+    if upgrade_authority(program_id, program_data_account)? != Some(*authority_account.key) {
+        return Err(ColabioError::NotConfigAdmin.into());
+    }
+    
+    // Verify the config account is the Config PDA
+    let (config_key, config_bump) = find_config_address(program_id);
+    if config_key != *config_account.key {
+        return Err(ColabioError::InvalidConfigAccount.into());
+    }
+    check_uninitialized(config_account, program_id)?;
+    
+    params.validate()?;
+    
+    // Create the Config account
+    create_pda_account(
+        authority_account,
+        config_account,
+        system_program,
+        program_id,
+        Config::LEN,
+        &[CONFIG_SEED, &[config_bump]],
+    )?;
+    
+    let config_data = Config {
+        account_type: AccountType::Config,
+        version: Config::VERSION,
+        admin: *authority_account.key,
+        params,
+        bump: config_bump,
+    };
+    
+    // Serialize and save config data
+    config_data.serialize(&mut *config_account.data.borrow_mut())?;
+    
+    msg!("Config initialized");
+    Ok(())
+}
+
+fn process_update_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_admin: Option<Pubkey>,
+    params: ConfigParams,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let admin_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    
+    // Verify admin is signer
+    if !admin_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Load config data and verify the admin
+    let mut config_data = Config::load_checked(config_account, program_id)?;
+    if config_data.admin != *admin_account.key {
+        return Err(ColabioError::NotConfigAdmin.into());
+    }
+    
+    params.validate()?;
+    
+    // Apply the new settings
+    config_data.params = params;
+    if let Some(new_admin) = new_admin {
+        config_data.admin = new_admin;
+    }
+    
+    // Save updated config data
+    config_data.serialize(&mut *config_account.data.borrow_mut())?;
+    
+    msg!("Config updated");
+    Ok(())
+}
+
+fn process_finalize_funding(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let project_account = next_account_info(accounts_iter)?;
+    
+    // Load project data, verifying owner, discriminator and version
+    let mut project_data = Project::load(project_account, program_id)?;
+    
+    // Check project status
+    if project_data.status != ProjectStatus::Active {
+        return Err(ColabioError::ProjectNotActive.into());
+    }
+    
+    // Check the funding period is over
+    let current_time = current_timestamp()?;
+    
+    if current_time <= project_data.end_time {
+        return Err(ColabioError::FundingPeriodNotEnded.into());
+    }
+    
+    // Settle the funding period according to the project's mode
+    let funded = match project_data.funding_mode {
+        FundingMode::AllOrNothing => project_data.ledger.total_deposited >= project_data.goal_amount,
+        FundingMode::Flexible => project_data.ledger.total_deposited > 0,
+    };
+    project_data.status = if funded {
+        ProjectStatus::Funded
+    } else {
+        ProjectStatus::Failed
+    };
+    
+    // Save updated project data
+    save_project(project_account, &project_data)?;
+    
+    msg!("Funding finalized: {}", if funded { "funded" } else { "failed" });
+    Ok(())
+}
+
+fn process_vote_milestone(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    milestone_index: u8,
+    approve: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let voter_account = next_account_info(accounts_iter)?;
+    let project_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let contribution_account = next_account_info(accounts_iter)?;
+    let milestone_vote_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    
+    // Verify voter is signer
+    if !voter_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Load project, config and contribution data
+    let mut project_data = Project::load(project_account, program_id)?;
+    let config = Config::load_checked(config_account, program_id)?;
+    let contribution_data = Contribution::load(contribution_account, program_id)?;
+    
+    // Verify the contribution backs this project and still has its weight
+    if contribution_data.project != *project_account.key {
+        return Err(ColabioError::ContributionMismatch.into());
+    }
+    if contribution_data.refunded {
+        return Err(ColabioError::AlreadyRefunded.into());
+    }
+    
+    // Verify the signer made the contribution or holds its receipt
+    let receipt_token_account = match contribution_data.receipt_mint {
+        Some(_) => Some(next_account_info(accounts_iter)?),
+        None => None,
+    };
+    check_contribution_authority(voter_account.key, &contribution_data, receipt_token_account)?;
+    
+    // Check project status
+    if project_data.status != ProjectStatus::Active && project_data.status != ProjectStatus::Funded {
+        return Err(ColabioError::ProjectNotActive.into());
+    }
+    
+    // Check milestone index
+    if milestone_index as usize >= project_data.milestones.len() {
+        return Err(ColabioError::InvalidMilestoneIndex.into());
+    }
+    
+    // Check the milestone is still awaiting validation
+    if project_data.milestones[milestone_index as usize].status != MilestoneStatus::Pending {
+        return Err(ColabioError::MilestoneNotPending.into());
+    }
+    
+    // Verify the vote account is this contribution's vote record PDA for the
+    // milestone's current round
+    let round = project_data.milestones[milestone_index as usize].round;
+    let (milestone_vote_key, milestone_vote_bump) =
+        find_milestone_vote_address(program_id, contribution_account.key, milestone_index, round);
+    if milestone_vote_key != *milestone_vote_account.key {
+        return Err(ColabioError::InvalidMilestoneVoteAccount.into());
+    }
+    
+    // Each contribution votes once per validation round
+    if milestone_vote_account.owner == program_id {
+        return Err(ColabioError::AlreadyVotedOnMilestone.into());
+    }
+    
+    // Create the milestone vote record
+    create_pda_account(
+        voter_account,
+        milestone_vote_account,
+        system_program,
+        program_id,
+        MilestoneVote::LEN,
+        &[
+            MILESTONE_VOTE_SEED,
+            contribution_account.key.as_ref(),
+            &[milestone_index],
+            &round.to_le_bytes(),
+            &[milestone_vote_bump],
+        ],
+    )?;
+    
+    // Tally the contribution's weight and re-check the milestone
+    let current_time = current_timestamp()?;
+    let weight = contribution_data.amount;
+    let milestone = &mut project_data.milestones[milestone_index as usize];
+    if approve {
+        milestone.funder_approve_weight =
+            milestone.funder_approve_weight.checked_add(weight).ok_or(ColabioError::ArithmeticOverflow)?;
+    } else {
+        milestone.funder_reject_weight =
+            milestone.funder_reject_weight.checked_add(weight).ok_or(ColabioError::ArithmeticOverflow)?;
+    }
+    milestone.refresh_status(&config.params, project_data.ledger.total_deposited, current_time);
+    
+    // Save updated project data
+    save_project(project_account, &project_data)?;
+    
+    // Create milestone vote record
+    let milestone_vote_data = MilestoneVote {
+        account_type: AccountType::MilestoneVote,
+        version: MilestoneVote::VERSION,
+        contribution: *contribution_account.key,
+        project: *project_account.key,
+        milestone_index,
+        round,
+        voter: *voter_account.key,
+        approve,
+        weight,
+        timestamp: current_time,
+        bump: milestone_vote_bump,
+    };
+    
+    // Serialize and save milestone vote data
+    milestone_vote_data.serialize(&mut *milestone_vote_account.data.borrow_mut())?;
+    
+    msg!("Milestone {} vote recorded with weight {}", milestone_index, weight);
+    Ok(())
+}
+
+fn process_tally_votes(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let project_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    
+    // Load project data, verifying owner, discriminator and version
+    let mut project_data = Project::load(project_account, program_id)?;
+    
+    // Check project status
+    if project_data.status != ProjectStatus::Pending {
+        return Err(ColabioError::ProjectNotPending.into());
+    }
+    
+    // Check the voting window is over
+    let current_time = current_timestamp()?;
+    
+    if current_time <= project_data.voting_end {
+        return Err(ColabioError::VotingNotEnded.into());
+    }
+    
+    // Decide the project by turnout and approval ratio
+    let config = Config::load_checked(config_account, program_id)?;
+    let total_votes = project_data
+        .approve_votes
+        .checked_add(project_data.reject_votes)
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    let approved = total_votes >= config.params.vote_quorum
+        && project_data.approve_votes as u64 * BPS_DENOMINATOR
+            >= config.params.approval_ratio_bps as u64 * total_votes as u64;
+    
+    if approved {
+        // Start the funding period now, keeping its original duration
+        let duration = project_data
+            .end_time
+            .checked_sub(project_data.start_time)
+            .ok_or(ColabioError::ArithmeticOverflow)?;
+        project_data.start_time = current_time;
+        project_data.end_time = current_time.checked_add(duration).ok_or(ColabioError::ArithmeticOverflow)?;
+        project_data.status = ProjectStatus::Active;
+        project_data.activated_at = current_time;
+    } else {
+        project_data.status = ProjectStatus::Cancelled;
+    }
+    
+    // Save updated project data
+    save_project(project_account, &project_data)?;
+    
+    msg!("Votes tallied: {}", if approved { "approved" } else { "rejected" });
+    Ok(())
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
-pub enum ProjectStatus {
-    Pending,
-    Active,
-    Completed,
-    Cancelled,
+fn process_register_validator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let validator_account = next_account_info(accounts_iter)?;
+    let validator_stake_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    
+    // Verify validator is signer
+    if !validator_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let config = Config::load_checked(config_account, program_id)?;
+    
+    // Load the existing stake, or create the stake PDA on first registration
+    let mut validator_stake = if validator_stake_account.owner == program_id {
+        let validator_stake =
+            ValidatorStake::load_checked(validator_stake_account, program_id, validator_account.key)?;
+        if validator_stake.unbonding_end != 0 {
+            return Err(ColabioError::ValidatorUnbonding.into());
+        }
+        validator_stake
+    } else {
+        let (stake_key, stake_bump) = find_validator_stake_address(program_id, validator_account.key);
+        if stake_key != *validator_stake_account.key {
+            return Err(ColabioError::InvalidValidatorStakeAccount.into());
+        }
+        create_pda_account(
+            validator_account,
+            validator_stake_account,
+            system_program,
+            program_id,
+            ValidatorStake::LEN,
+            &[VALIDATOR_SEED, validator_account.key.as_ref(), &[stake_bump]],
+        )?;
+        ValidatorStake {
+            account_type: AccountType::ValidatorStake,
+            version: ValidatorStake::VERSION,
+            validator: *validator_account.key,
+            stake: 0,
+            unbonding_end: 0,
+            bump: stake_bump,
+        }
+    };
+    
+    // Bond the stake
+    invoke(
+        &system_instruction::transfer(
+            validator_account.key,
+            validator_stake_account.key,
+            amount,
+        ),
+        &[validator_account.clone(), validator_stake_account.clone(), system_program.clone()],
+    )?;
+    validator_stake.stake = validator_stake.stake
+        .checked_add(amount)
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    
+    if validator_stake.stake < config.params.min_validator_stake {
+        return Err(ColabioError::InsufficientValidatorStake.into());
+    }
+    
+    // Save validator stake data
+    validator_stake.serialize(&mut *validator_stake_account.data.borrow_mut())?;
+    
+    msg!("Validator registered with stake {}", validator_stake.stake);
+    Ok(())
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
-pub struct Contribution {
-    pub contributor: Pubkey,
-    pub project: Pubkey,
-    pub amount: u64,
-    pub timestamp: u64,
+fn process_deregister_validator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let validator_account = next_account_info(accounts_iter)?;
+    let validator_stake_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    
+    // Verify validator is signer
+    if !validator_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let config = Config::load_checked(config_account, program_id)?;
+    let mut validator_stake =
+        ValidatorStake::load_checked(validator_stake_account, program_id, validator_account.key)?;
+    
+    if validator_stake.unbonding_end != 0 {
+        return Err(ColabioError::ValidatorUnbonding.into());
+    }
+    
+    // Start unbonding
+    let current_time = current_timestamp()?;
+    validator_stake.unbonding_end = current_time
+        .checked_add(config.params.unbonding_period)
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    
+    // Save validator stake data
+    validator_stake.serialize(&mut *validator_stake_account.data.borrow_mut())?;
+    
+    msg!("Validator unbonding until {}", validator_stake.unbonding_end);
+    Ok(())
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
-pub struct Validation {
-    pub validator: Pubkey,
-    pub project: Pubkey,
-    pub milestone_index: u8,
-    pub timestamp: u64,
+fn process_withdraw_validator_stake(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let validator_account = next_account_info(accounts_iter)?;
+    let validator_stake_account = next_account_info(accounts_iter)?;
+    
+    // Verify validator is signer
+    if !validator_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let validator_stake =
+        ValidatorStake::load_checked(validator_stake_account, program_id, validator_account.key)?;
+    
+    // Check unbonding has finished
+    let current_time = current_timestamp()?;
+    
+    if validator_stake.unbonding_end == 0 || current_time < validator_stake.unbonding_end {
+        return Err(ColabioError::UnbondingNotComplete.into());
+    }
+    
+    // Close the stake account, returning the stake and rent to the validator
+    let lamports = validator_stake_account.lamports();
+    **validator_stake_account.lamports.borrow_mut() = 0;
+    let validator_lamports = validator_account
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    **validator_account.lamports.borrow_mut() = validator_lamports;
+    validator_stake_account.data.borrow_mut().fill(0);
+    
+    msg!("Validator stake withdrawn: {}", validator_stake.stake);
+    Ok(())
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
-pub struct Vote {
-    pub voter: Pubkey,
-    pub project: Pubkey,
-    pub approve: bool,
-    pub timestamp: u64,
+fn process_overturn_milestone(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    milestone_index: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let admin_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let project_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    
+    // Verify admin is signer
+    if !admin_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Load config data and verify the admin
+    let config = Config::load_checked(config_account, program_id)?;
+    if config.admin != *admin_account.key {
+        return Err(ColabioError::NotConfigAdmin.into());
+    }
+    
+    // Load project data, verifying owner, discriminator and version
+    let mut project_data = Project::load(project_account, program_id)?;
+    
+    // Check milestone index
+    if milestone_index as usize >= project_data.milestones.len() {
+        return Err(ColabioError::InvalidMilestoneIndex.into());
+    }
+    
+    // Only validated, unreleased milestones can be overturned
+    let milestone = &mut project_data.milestones[milestone_index as usize];
+    if milestone.status != MilestoneStatus::Validated {
+        return Err(ColabioError::MilestoneNotValidated.into());
+    }
+    
+    // Open disputes are settled through ArbitrateDispute instead
+    if milestone.disputed {
+        return Err(ColabioError::MilestoneDisputed.into());
+    }
+    
+    // Reset the milestone into a fresh validation round
+    project_data.overturn_milestone(milestone_index as usize)?;
+    
+    // Save updated project data, growing the account for the overturned round
+    write_project(project_account, &project_data, admin_account, system_program)?;
+    
+    msg!("Milestone {} overturned", milestone_index);
+    Ok(())
 }
 
-// Process functions for each instruction
-fn process_initialize_project(
+fn process_slash_validator(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    title: String,
-    description: String,
-    goal_amount: u64,
-    duration: u64,
-    milestones: Vec<Milestone>,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     
     // Get accounts
-    let creator_account = next_account_info(accounts_iter)?;
     let project_account = next_account_info(accounts_iter)?;
-    let rent_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let validation_account = next_account_info(accounts_iter)?;
+    let validator_stake_account = next_account_info(accounts_iter)?;
+    let fee_recipient_account = next_account_info(accounts_iter)?;
+    
+    // Load and cross-check the project, validation and stake
+    let project_data = Project::load(project_account, program_id)?;
+    let config = Config::load_checked(config_account, program_id)?;
+    let mut validation_data = Validation::load(validation_account, program_id)?;
+    
+    if validation_data.project != *project_account.key {
+        return Err(ColabioError::InvalidValidationAccount.into());
+    }
+    let mut validator_stake =
+        ValidatorStake::load_checked(validator_stake_account, program_id, &validation_data.validator)?;
+    
+    if config.params.fee_recipient != *fee_recipient_account.key {
+        return Err(ColabioError::InvalidFeeRecipient.into());
+    }
+    
+    // Only approvals from an overturned round are slashable, once each
+    if validation_data.slashed {
+        return Err(ColabioError::ValidationAlreadySlashed.into());
+    }
+    let milestone = project_data
+        .milestones
+        .get(validation_data.milestone_index as usize)
+        .ok_or(ColabioError::InvalidMilestoneIndex)?;
+    if !validation_data.approve || !milestone.overturned_rounds.contains(&validation_data.round) {
+        return Err(ColabioError::ValidationNotOverturned.into());
+    }
+    
+    // Move the slashed share of the stake to the fee recipient
+    let amount = (validator_stake.stake as u128 * config.params.slash_bps as u128
+        / BPS_DENOMINATOR as u128) as u64;
+    move_lamports(validator_stake_account, fee_recipient_account, amount)?;
+    validator_stake.stake = validator_stake.stake
+        .checked_sub(amount)
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    validation_data.slashed = true;
+    
+    // Save validator stake and validation data
+    validator_stake.serialize(&mut *validator_stake_account.data.borrow_mut())?;
+    validation_data.serialize(&mut *validation_account.data.borrow_mut())?;
+    
+    msg!("Validator {} slashed {}", validation_data.validator, amount);
+    Ok(())
+}
+
+fn process_open_dispute(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    milestone_index: u8,
+    evidence_hash: [u8; 32],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let challenger_account = next_account_info(accounts_iter)?;
+    let project_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let contribution_account = next_account_info(accounts_iter)?;
+    let dispute_account = next_account_info(accounts_iter)?;
     let system_program = next_account_info(accounts_iter)?;
     
-    // Verify creator is signer
-    if !creator_account.is_signer {
+    // Verify challenger is signer
+    if !challenger_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
     
-    // Verify program owns the project account
-    if project_account.owner != program_id {
-        // Create the project account
-        let rent = Rent::from_account_info(rent_account)?;
-        let space = 1000; // Estimate space needed for the project data
-        let lamports = rent.minimum_balance(space);
-        
-        invoke(
-            &system_instruction::create_account(
-                creator_account.key,
-                project_account.key,
-                lamports,
-                space as u64,
-                program_id,
-            ),
-            &[creator_account.clone(), project_account.clone(), system_program.clone()],
-        )?;
+    // Load project, config and contribution data
+    let mut project_data = Project::load(project_account, program_id)?;
+    let config = Config::load_checked(config_account, program_id)?;
+    let contribution_data = Contribution::load(contribution_account, program_id)?;
+    
+    // Verify the contribution backs this project
+    if contribution_data.project != *project_account.key {
+        return Err(ColabioError::ContributionMismatch.into());
+    }
+    if contribution_data.refunded {
+        return Err(ColabioError::AlreadyRefunded.into());
     }
     
-    // Get current timestamp
-    let clock = solana_program::clock::Clock::get()?;
-    let current_time = clock.unix_timestamp as u64;
+    // Verify the signer made the contribution or holds its receipt
+    let receipt_token_account = match contribution_data.receipt_mint {
+        Some(_) => Some(next_account_info(accounts_iter)?),
+        None => None,
+    };
+    check_contribution_authority(challenger_account.key, &contribution_data, receipt_token_account)?;
     
-    // Create project data
-    let project_data = Project {
-        creator: *creator_account.key,
-        title,
-        description,
-        goal_amount,
-        raised_amount: 0,
-        start_time: current_time,
-        end_time: current_time + duration,
-        milestones,
-        status: ProjectStatus::Pending,
-        approve_votes: 0,
-        reject_votes: 0,
+    // Check milestone index
+    if milestone_index as usize >= project_data.milestones.len() {
+        return Err(ColabioError::InvalidMilestoneIndex.into());
+    }
+    
+    // Only validated milestones inside their challenge window can be disputed
+    let current_time = current_timestamp()?;
+    let milestone = &mut project_data.milestones[milestone_index as usize];
+    
+    if milestone.status != MilestoneStatus::Validated {
+        return Err(ColabioError::MilestoneNotValidated.into());
+    }
+    if milestone.disputed {
+        return Err(ColabioError::MilestoneDisputed.into());
+    }
+    let challenge_end = milestone
+        .validated_at
+        .checked_add(config.params.challenge_period)
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    if current_time > challenge_end {
+        return Err(ColabioError::ChallengePeriodEnded.into());
+    }
+    
+    // Verify the dispute account is this round's dispute PDA
+    let round = milestone.round;
+    let (dispute_key, dispute_bump) =
+        find_dispute_address(program_id, project_account.key, milestone_index, round);
+    if dispute_key != *dispute_account.key {
+        return Err(ColabioError::InvalidDisputeAccount.into());
+    }
+    check_uninitialized(dispute_account, program_id)?;
+    
+    // Create the dispute and post the bond into it
+    create_pda_account(
+        challenger_account,
+        dispute_account,
+        system_program,
+        program_id,
+        Dispute::LEN,
+        &[
+            DISPUTE_SEED,
+            project_account.key.as_ref(),
+            &[milestone_index],
+            &round.to_le_bytes(),
+            &[dispute_bump],
+        ],
+    )?;
+    let bond = config.params.dispute_bond;
+    invoke(
+        &system_instruction::transfer(challenger_account.key, dispute_account.key, bond),
+        &[challenger_account.clone(), dispute_account.clone(), system_program.clone()],
+    )?;
+    
+    // Hold the milestone until the dispute is resolved
+    milestone.disputed = true;
+    
+    // Save updated project data
+    save_project(project_account, &project_data)?;
+    
+    let dispute_data = Dispute {
+        account_type: AccountType::Dispute,
+        version: Dispute::VERSION,
+        project: *project_account.key,
+        milestone_index,
+        round,
+        challenger: *challenger_account.key,
+        evidence_hash,
+        bond,
+        opened_at: current_time,
+        voting_end: current_time
+            .checked_add(config.params.dispute_period)
+            .ok_or(ColabioError::ArithmeticOverflow)?,
+        uphold_weight: 0,
+        dismiss_weight: 0,
+        status: DisputeStatus::Open,
+        bump: dispute_bump,
     };
     
-    // Serialize and save project data
-    project_data.serialize(&mut *project_account.data.borrow_mut())?;
+    // Serialize and save dispute data
+    dispute_data.serialize(&mut *dispute_account.data.borrow_mut())?;
     
-    msg!("Project initialized: {}", project_data.title);
+    msg!("Milestone {} disputed", milestone_index);
     Ok(())
 }
 
-fn process_contribute(
+fn process_vote_dispute(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    amount: u64,
+    uphold: bool,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     
     // Get accounts
-    let contributor_account = next_account_info(accounts_iter)?;
+    let voter_account = next_account_info(accounts_iter)?;
     let project_account = next_account_info(accounts_iter)?;
+    let dispute_account = next_account_info(accounts_iter)?;
     let contribution_account = next_account_info(accounts_iter)?;
+    let dispute_vote_account = next_account_info(accounts_iter)?;
     let system_program = next_account_info(accounts_iter)?;
     
-    // Verify contributor is signer
-    if !contributor_account.is_signer {
+    // Verify voter is signer
+    if !voter_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
     
-    // Deserialize project data
-    let mut project_data = Project::try_from_slice(&project_account.data.borrow())?;
+    // Load dispute and contribution data
+    let mut dispute_data = Dispute::load(dispute_account, program_id)?;
+    let contribution_data = Contribution::load(contribution_account, program_id)?;
     
-    // Check project status
-    if project_data.status != ProjectStatus::Active {
-        return Err(ProgramError::InvalidAccountData);
+    // Verify the dispute and contribution both belong to this project
+    if dispute_data.project != *project_account.key {
+        return Err(ColabioError::InvalidDisputeAccount.into());
+    }
+    if contribution_data.project != *project_account.key {
+        return Err(ColabioError::ContributionMismatch.into());
+    }
+    if contribution_data.refunded {
+        return Err(ColabioError::AlreadyRefunded.into());
     }
     
-    // Check if project is still accepting contributions
-    let clock = solana_program::clock::Clock::get()?;
-    let current_time = clock.unix_timestamp as u64;
+    // Verify the signer made the contribution or holds its receipt
+    let receipt_token_account = match contribution_data.receipt_mint {
+        Some(_) => Some(next_account_info(accounts_iter)?),
+        None => None,
+    };
+    check_contribution_authority(voter_account.key, &contribution_data, receipt_token_account)?;
     
-    if current_time > project_data.end_time {
-        return Err(ProgramError::InvalidAccountData);
+    // Check the dispute is open for voting
+    let current_time = current_timestamp()?;
+    
+    if dispute_data.status != DisputeStatus::Open {
+        return Err(ColabioError::DisputeNotOpen.into());
+    }
+    if current_time > dispute_data.voting_end {
+        return Err(ColabioError::DisputeVotingClosed.into());
     }
     
-    // Create contribution account if needed
-    if contribution_account.owner != program_id {
-        let rent = Rent::get()?;
-        let space = 100; // Estimate space needed for contribution data
-        let lamports = rent.minimum_balance(space);
-        
-        invoke(
-            &system_instruction::create_account(
-                contributor_account.key,
-                contribution_account.key,
-                lamports,
-                space as u64,
-                program_id,
-            ),
-            &[contributor_account.clone(), contribution_account.clone(), system_program.clone()],
-        )?;
+    // Verify the vote account is this contribution's dispute vote PDA
+    let (dispute_vote_key, dispute_vote_bump) =
+        find_dispute_vote_address(program_id, dispute_account.key, contribution_account.key);
+    if dispute_vote_key != *dispute_vote_account.key {
+        return Err(ColabioError::InvalidDisputeVoteAccount.into());
     }
     
-    // Transfer SOL from contributor to project account
-    invoke(
-        &system_instruction::transfer(
-            contributor_account.key,
-            project_account.key,
-            amount,
-        ),
-        &[contributor_account.clone(), project_account.clone(), system_program.clone()],
+    // Each contribution votes once per dispute
+    if dispute_vote_account.owner == program_id {
+        return Err(ColabioError::AlreadyVotedOnDispute.into());
+    }
+    
+    // Create the dispute vote record
+    create_pda_account(
+        voter_account,
+        dispute_vote_account,
+        system_program,
+        program_id,
+        DisputeVote::LEN,
+        &[
+            DISPUTE_VOTE_SEED,
+            dispute_account.key.as_ref(),
+            contribution_account.key.as_ref(),
+            &[dispute_vote_bump],
+        ],
     )?;
     
-    // Update project raised amount
-    project_data.raised_amount += amount;
-    project_data.serialize(&mut *project_account.data.borrow_mut())?;
+    // Tally the contribution's weight
+    let weight = contribution_data.amount;
+    if uphold {
+        dispute_data.uphold_weight =
+            dispute_data.uphold_weight.checked_add(weight).ok_or(ColabioError::ArithmeticOverflow)?;
+    } else {
+        dispute_data.dismiss_weight =
+            dispute_data.dismiss_weight.checked_add(weight).ok_or(ColabioError::ArithmeticOverflow)?;
+    }
     
-    // Create contribution record
-    let contribution_data = Contribution {
-        contributor: *contributor_account.key,
-        project: *project_account.key,
-        amount,
+    // Save updated dispute data
+    dispute_data.serialize(&mut *dispute_account.data.borrow_mut())?;
+    
+    let dispute_vote_data = DisputeVote {
+        account_type: AccountType::DisputeVote,
+        version: DisputeVote::VERSION,
+        dispute: *dispute_account.key,
+        contribution: *contribution_account.key,
+        voter: *voter_account.key,
+        uphold,
+        weight,
         timestamp: current_time,
+        bump: dispute_vote_bump,
     };
     
-    // Serialize and save contribution data
-    contribution_data.serialize(&mut *contribution_account.data.borrow_mut())?;
+    // Serialize and save dispute vote data
+    dispute_vote_data.serialize(&mut *dispute_vote_account.data.borrow_mut())?;
     
-    msg!("Contribution of {} lamports received", amount);
+    msg!("Dispute vote recorded with weight {}", weight);
     Ok(())
 }
 
-fn process_validate_milestone(
+/// Settles a dispute by tally when `ruling` is `None`, or by the Config
+/// admin's ruling otherwise
+fn process_resolve_dispute(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    milestone_index: u8,
+    ruling: Option<bool>,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     
     // Get accounts
-    let validator_account = next_account_info(accounts_iter)?;
     let project_account = next_account_info(accounts_iter)?;
-    let validation_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let dispute_account = next_account_info(accounts_iter)?;
+    let challenger_account = next_account_info(accounts_iter)?;
+    let fee_recipient_account = next_account_info(accounts_iter)?;
+    let payer_account = next_account_info(accounts_iter)?;
     let system_program = next_account_info(accounts_iter)?;
     
-    // Verify validator is signer
-    if !validator_account.is_signer {
+    // Verify payer is signer
+    if !payer_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
     
-    // Deserialize project data
-    let mut project_data = Project::try_from_slice(&project_account.data.borrow())?;
+    // Load project, config and dispute data
+    let mut project_data = Project::load(project_account, program_id)?;
+    let config = Config::load_checked(config_account, program_id)?;
+    let mut dispute_data = Dispute::load(dispute_account, program_id)?;
     
-    // Check project status
-    if project_data.status != ProjectStatus::Active {
-        return Err(ProgramError::InvalidAccountData);
+    // Verify the dispute, challenger and fee recipient
+    if dispute_data.project != *project_account.key {
+        return Err(ColabioError::InvalidDisputeAccount.into());
     }
-    
-    // Check milestone index
-    if milestone_index as usize >= project_data.milestones.len() {
-        return Err(ProgramError::InvalidArgument);
+    if dispute_data.challenger != *challenger_account.key {
+        return Err(ColabioError::InvalidDisputeAccount.into());
     }
-    
-    // Create validation account if needed
-    if validation_account.owner != program_id {
-        let rent = Rent::get()?;
-        let space = 100; // Estimate space needed for validation data
-        let lamports = rent.minimum_balance(space);
-        
-        invoke(
-            &system_instruction::create_account(
-                validator_account.key,
-                validation_account.key,
-                lamports,
-                space as u64,
-                program_id,
-            ),
-            &[validator_account.clone(), validation_account.clone(), system_program.clone()],
-        )?;
+    if config.params.fee_recipient != *fee_recipient_account.key {
+        return Err(ColabioError::InvalidFeeRecipient.into());
+    }
+    if dispute_data.status != DisputeStatus::Open {
+        return Err(ColabioError::DisputeNotOpen.into());
     }
     
-    // Update milestone validations
-    project_data.milestones[milestone_index as usize].validations += 1;
+    // Decide the dispute by arbiter ruling or contributor vote
+    let uphold = match ruling {
+        Some(uphold) => {
+            if config.admin != *payer_account.key {
+                return Err(ColabioError::NotConfigAdmin.into());
+            }
+            uphold
+        }
+        None => {
+            let current_time = current_timestamp()?;
+            if current_time <= dispute_data.voting_end {
+                return Err(ColabioError::DisputeVotingNotEnded.into());
+            }
+            let total_weight = dispute_data.uphold_weight as u128 + dispute_data.dismiss_weight as u128;
+            let quorum_met = total_weight * BPS_DENOMINATOR as u128
+                >= project_data.ledger.total_deposited as u128 * config.params.funder_quorum_bps as u128;
+            quorum_met && dispute_data.uphold_weight > dispute_data.dismiss_weight
+        }
+    };
     
-    // Check if milestone has enough validations to be considered complete
-    // For simplicity, we'll say 3 validations are required
-    if project_data.milestones[milestone_index as usize].validations >= 3 {
-        project_data.milestones[milestone_index as usize].completed = true;
+    // Overturn or confirm the milestone
+    let milestone_index = dispute_data.milestone_index as usize;
+    if uphold {
+        project_data.overturn_milestone(milestone_index)?;
+    } else {
+        project_data.milestones[milestone_index].disputed = false;
     }
     
-    // Save updated project data
-    project_data.serialize(&mut *project_account.data.borrow_mut())?;
-    
-    // Create validation record
-    let clock = solana_program::clock::Clock::get()?;
-    let current_time = clock.unix_timestamp as u64;
+    // Return the bond to an upheld challenger, or forfeit it
+    let bond_recipient = if uphold { challenger_account } else { fee_recipient_account };
+    move_lamports(dispute_account, bond_recipient, dispute_data.bond)?;
     
-    let validation_data = Validation {
-        validator: *validator_account.key,
-        project: *project_account.key,
-        milestone_index,
-        timestamp: current_time,
+    dispute_data.status = if uphold {
+        DisputeStatus::Upheld
+    } else {
+        DisputeStatus::Dismissed
     };
     
-    // Serialize and save validation data
-    validation_data.serialize(&mut *validation_account.data.borrow_mut())?;
+    // Save updated project and dispute data; an upheld dispute grows the
+    // project account for the overturned round
+    write_project(project_account, &project_data, payer_account, system_program)?;
+    dispute_data.serialize(&mut *dispute_account.data.borrow_mut())?;
     
-    msg!("Milestone {} validated", milestone_index);
+    msg!("Dispute {}", if uphold { "upheld" } else { "dismissed" });
     Ok(())
 }
 
-fn process_release_funds(
+fn process_submit_milestone_evidence(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     milestone_index: u8,
+    evidence: Vec<MilestoneEvidence>,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     
     // Get accounts
     let creator_account = next_account_info(accounts_iter)?;
     let project_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
     let system_program = next_account_info(accounts_iter)?;
     
     // Verify creator is signer
@@ -516,64 +4492,120 @@ fn process_release_funds(
         return Err(ProgramError::MissingRequiredSignature);
     }
     
-    // Deserialize project data
-    let mut project_data = Project::try_from_slice(&project_account.data.borrow())?;
+    // Load project data, verifying owner, discriminator and version
+    let mut project_data = Project::load(project_account, program_id)?;
+    let config = Config::load_checked(config_account, program_id)?;
     
     // Verify creator is project owner
     if project_data.creator != *creator_account.key {
-        return Err(ProgramError::InvalidAccountData);
+        return Err(ColabioError::NotProjectCreator.into());
     }
     
     // Check project status
-    if project_data.status != ProjectStatus::Active {
-        return Err(ProgramError::InvalidAccountData);
+    if project_data.status != ProjectStatus::Active && project_data.status != ProjectStatus::Funded {
+        return Err(ColabioError::ProjectNotActive.into());
     }
     
     // Check milestone index
     if milestone_index as usize >= project_data.milestones.len() {
-        return Err(ProgramError::InvalidArgument);
+        return Err(ColabioError::InvalidMilestoneIndex.into());
     }
     
-    // Check if milestone is completed
-    if !project_data.milestones[milestone_index as usize].completed {
-        return Err(ProgramError::InvalidAccountData);
+    // Evidence can be added while the milestone awaits its first review, or
+    // resubmitted in a new round after a rejection
+    let milestone = &mut project_data.milestones[milestone_index as usize];
+    match milestone.status {
+        MilestoneStatus::Pending => {
+            if milestone.validations > 0 || milestone.rejections > 0 {
+                return Err(ColabioError::EvidenceLocked.into());
+            }
+        }
+        MilestoneStatus::Rejected => {
+            if milestone.resubmissions >= config.params.max_resubmissions {
+                return Err(ColabioError::ResubmissionLimitReached.into());
+            }
+            milestone.resubmissions = milestone.resubmissions
+                .checked_add(1)
+                .ok_or(ColabioError::ArithmeticOverflow)?;
+            milestone.evidence.clear();
+            milestone.start_round()?;
+        }
+        _ => return Err(ColabioError::MilestoneNotPending.into()),
     }
     
-    // Get milestone amount
-    let amount = project_data.milestones[milestone_index as usize].amount;
+    // Check the submission against the evidence limits
+    if evidence.is_empty()
+        || milestone.evidence.len() + evidence.len() > MAX_MILESTONE_EVIDENCE
+        || evidence.iter().any(|entry| entry.uri.is_empty() || entry.uri.len() > MAX_EVIDENCE_URI_LEN)
+    {
+        return Err(ColabioError::InvalidEvidence.into());
+    }
     
-    // Transfer funds from project account to creator
-    let project_seeds = &[
-        b"project",
-        project_account.key.as_ref(),
-        &[0], // Bump seed
-    ];
-    let project_signer = &[&project_seeds[..]];
+    // Append the evidence
+    let count = evidence.len();
+    milestone.evidence.extend(evidence);
     
-    invoke_signed(
-        &system_instruction::transfer(
-            project_account.key,
-            creator_account.key,
-            amount,
-        ),
-        &[project_account.clone(), creator_account.clone(), system_program.clone()],
-        project_signer,
-    )?;
+    // Save updated project data, reallocating if it no longer fits
+    write_project(project_account, &project_data, creator_account, system_program)?;
     
-    // Check if all milestones are completed
-    let all_completed = project_data.milestones.iter().all(|m| m.completed);
-    if all_completed {
-        project_data.status = ProjectStatus::Completed;
+    msg!("Milestone {} evidence submitted: {} entries", milestone_index, count);
+    Ok(())
+}
+
+fn process_mark_default(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    milestone_index: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    // Get accounts
+    let project_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    
+    // Load project data, verifying owner, discriminator and version
+    let mut project_data = Project::load(project_account, program_id)?;
+    let config = Config::load_checked(config_account, program_id)?;
+    
+    // Only projects that can pay out milestones can default; all-or-nothing
+    // projects must be finalized first
+    if !project_data.can_release_funds() {
+        if project_data.status == ProjectStatus::Active {
+            return Err(ColabioError::FundingNotFinalized.into());
+        }
+        return Err(ColabioError::ProjectNotActive.into());
+    }
+    
+    // Check milestone index
+    if milestone_index as usize >= project_data.milestones.len() {
+        return Err(ColabioError::InvalidMilestoneIndex.into());
+    }
+    if project_data.milestones[milestone_index as usize].status == MilestoneStatus::Released {
+        return Err(ColabioError::MilestoneAlreadyReleased.into());
     }
     
+    // Check the milestone is past its due date and the grace period
+    let current_time = current_timestamp()?;
+    let default_time = project_data
+        .milestone_due_date(milestone_index as usize)?
+        .checked_add(config.params.milestone_grace_period)
+        .ok_or(ColabioError::ArithmeticOverflow)?;
+    
+    if current_time <= default_time {
+        return Err(ColabioError::MilestoneNotOverdue.into());
+    }
+    
+    // Default the project, which opens refunds of the remaining escrow
+    project_data.status = ProjectStatus::Defaulted;
+    
     // Save updated project data
-    project_data.serialize(&mut *project_account.data.borrow_mut())?;
+    save_project(project_account, &project_data)?;
     
-    msg!("Released {} lamports for milestone {}", amount, milestone_index);
+    msg!("Project defaulted on milestone {}", milestone_index);
     Ok(())
 }
 
-fn process_cancel_project(
+fn process_reclaim_bond(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
@@ -582,111 +4614,683 @@ fn process_cancel_project(
     // Get accounts
     let creator_account = next_account_info(accounts_iter)?;
     let project_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let bond_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
     
     // Verify creator is signer
     if !creator_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
     
-    // Deserialize project data
-    let mut project_data = Project::try_from_slice(&project_account.data.borrow())?;
+    // Load project data, verifying owner, discriminator and version
+    let mut project_data = Project::load(project_account, program_id)?;
     
     // Verify creator is project owner
     if project_data.creator != *creator_account.key {
-        return Err(ProgramError::InvalidAccountData);
+        return Err(ColabioError::NotProjectCreator.into());
     }
     
-    // Check project status
-    if project_data.status != ProjectStatus::Pending && project_data.status != ProjectStatus::Active {
-        return Err(ProgramError::InvalidAccountData);
+    // Verify the vault and bond belong to this project
+    check_vault(program_id, project_account.key, &project_data, vault_account)?;
+    check_bond(program_id, project_account.key, &project_data, bond_account)?;
+    
+    // Check the bond is owed back to the creator
+    if !project_data.bond_reclaimable() {
+        return Err(ColabioError::BondNotReclaimable.into());
     }
     
-    // Set project status to cancelled
-    project_data.status = ProjectStatus::Cancelled;
+    // Resolve the destination: the wallet for SOL, a token account for tokens
+    let token_escrow = next_token_escrow(accounts_iter, program_id, project_account.key, &project_data)?;
+    let bond_destination = match &token_escrow {
+        Some(escrow) => {
+            let creator_token_account = next_account_info(accounts_iter)?;
+            check_token_account(creator_token_account, escrow, creator_account.key)?;
+            creator_token_account
+        }
+        None => creator_account,
+    };
+    
+    // Return the whole bond
+    let amount = project_data.creator_bond;
+    if amount > 0 {
+        let bond_accounts = BondAccounts {
+            vault: vault_account,
+            bond: bond_account,
+            system_program,
+        };
+        pay_from_bond(
+            project_account.key,
+            &mut project_data,
+            &bond_accounts,
+            token_escrow.as_ref(),
+            bond_destination,
+            amount,
+        )?;
+    }
     
     // Save updated project data
-    project_data.serialize(&mut *project_account.data.borrow_mut())?;
+    save_project(project_account, &project_data)?;
     
-    msg!("Project cancelled");
+    msg!("Creator bond reclaimed: {}", amount);
     Ok(())
 }
 
-fn process_vote(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    approve: bool,
-) -> ProgramResult {
-    let accounts_iter = &mut accounts.iter();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::{clock::Clock, entrypoint::deserialize, program_stubs, system_program};
     
-    // Get accounts
-    let voter_account = next_account_info(accounts_iter)?;
-    let project_account = next_account_info(accounts_iter)?;
-    let vote_account = next_account_info(accounts_iter)?;
-    let system_program = next_account_info(accounts_iter)?;
+    /// Cluster time served by the clock sysvar stub
+    const NOW: i64 = 1_000_000;
     
-    // Verify voter is signer
-    if !voter_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+    /// Serves the rent sysvar, which `write_project` reads to grow accounts,
+    /// and a clock fixed at `NOW`
+    struct SysvarStubs;
+    
+    impl program_stubs::SyscallStubs for SysvarStubs {
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Rent) = Rent::default() };
+            solana_program::entrypoint::SUCCESS
+        }
+        
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Clock) = Clock { unix_timestamp: NOW, ..Clock::default() } };
+            solana_program::entrypoint::SUCCESS
+        }
     }
     
-    // Deserialize project data
-    let mut project_data = Project::try_from_slice(&project_account.data.borrow())?;
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        is_signer: bool,
+        lamports: u64,
+        data: Vec<u8>,
+    }
     
-    // Check project status
-    if project_data.status != ProjectStatus::Pending {
-        return Err(ProgramError::InvalidAccountData);
+    impl TestAccount {
+        fn new(key: Pubkey, owner: Pubkey, lamports: u64, data: Vec<u8>) -> Self {
+            TestAccount { key, owner, is_signer: false, lamports, data }
+        }
+        
+        fn signer(key: Pubkey) -> Self {
+            TestAccount { is_signer: true, ..Self::new(key, system_program::ID, 1_000_000_000, Vec::new()) }
+        }
     }
     
-    // Create vote account if needed
-    if vote_account.owner != program_id {
-        let rent = Rent::get()?;
-        let space = 100; // Estimate space needed for vote data
-        let lamports = rent.minimum_balance(space);
+    /// Lays out program input the way the loader does, including the realloc
+    /// headroom after each account's data, in an 8-byte aligned buffer
+    fn serialize_input(program_id: &Pubkey, accounts: &[TestAccount], instruction_data: &[u8]) -> Vec<u64> {
+        let mut input = (accounts.len() as u64).to_le_bytes().to_vec();
+        for account in accounts {
+            input.extend_from_slice(&[u8::MAX, account.is_signer as u8, 1, 0]);
+            input.extend_from_slice(&[0; 4]);
+            input.extend_from_slice(account.key.as_ref());
+            input.extend_from_slice(account.owner.as_ref());
+            input.extend_from_slice(&account.lamports.to_le_bytes());
+            input.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+            input.extend_from_slice(&account.data);
+            input.resize((input.len() + MAX_PERMITTED_DATA_INCREASE).next_multiple_of(8), 0);
+            input.extend_from_slice(&0u64.to_le_bytes());
+        }
+        input.extend_from_slice(&(instruction_data.len() as u64).to_le_bytes());
+        input.extend_from_slice(instruction_data);
+        input.extend_from_slice(program_id.as_ref());
         
-        invoke(
-            &system_instruction::create_account(
-                voter_account.key,
-                vote_account.key,
-                lamports,
-                space as u64,
-                program_id,
-            ),
-            &[voter_account.clone(), vote_account.clone(), system_program.clone()],
-        )?;
+        input
+            .chunks(8)
+            .map(|chunk| {
+                let mut word = [0; 8];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u64::from_ne_bytes(word)
+            })
+            .collect()
     }
     
-    // Update project votes
-    if approve {
-        project_data.approve_votes += 1;
-    } else {
-        project_data.reject_votes += 1;
+    /// Runs one instruction, returning its result and the account data the
+    /// loader would write back afterwards
+    fn run(
+        program_id: &Pubkey,
+        accounts: &[TestAccount],
+        instruction: ColabioInstruction,
+    ) -> (ProgramResult, Vec<Vec<u8>>) {
+        program_stubs::set_syscall_stubs(Box::new(SysvarStubs));
+        let mut input = serialize_input(program_id, accounts, &instruction.pack());
+        let (program_id, account_infos, instruction_data) =
+            unsafe { deserialize(input.as_mut_ptr() as *mut u8) };
+        let result = process_instruction(program_id, &account_infos, instruction_data);
+        drop(account_infos);
+        
+        // Serializing into an account advances its data slice, so read the
+        // data back from the input buffer, which also reflects any realloc
+        let (_, account_infos, _) = unsafe { deserialize(input.as_mut_ptr() as *mut u8) };
+        let data = account_infos.iter().map(|account| account.data.borrow().to_vec()).collect();
+        (result, data)
     }
     
-    // Check if project has enough votes to change status
-    // For simplicity, we'll say 10 approve votes are needed to activate
-    if project_data.approve_votes >= 10 {
-        project_data.status = ProjectStatus::Active;
-    } else if project_data.reject_votes >= 10 {
-        project_data.status = ProjectStatus::Cancelled;
+    fn config_params() -> ConfigParams {
+        ConfigParams {
+            vote_quorum: 1,
+            approval_ratio_bps: 5_000,
+            required_validations: 1,
+            min_duration: 1,
+            max_duration: 1_000_000,
+            fee_bps: 0,
+            fee_recipient: Pubkey::new_unique(),
+            funder_quorum_bps: 0,
+            funder_approval_ratio_bps: 0,
+            voting_period: 1,
+            min_validator_stake: 1,
+            unbonding_period: 1_001,
+            slash_bps: 1_000,
+            challenge_period: 1_000,
+            dispute_bond: 1_000,
+            dispute_period: 1,
+            rejection_threshold: 1,
+            max_resubmissions: 1,
+            milestone_grace_period: 0,
+            creator_bond_bps: 0,
+        }
     }
     
-    // Save updated project data
-    project_data.serialize(&mut *project_account.data.borrow_mut())?;
+    fn config_account(program_id: &Pubkey, admin: Pubkey) -> TestAccount {
+        let (config_key, bump) = find_config_address(program_id);
+        let config = Config {
+            account_type: AccountType::Config,
+            version: Config::VERSION,
+            admin,
+            params: config_params(),
+            bump,
+        };
+        TestAccount::new(config_key, *program_id, 1_000_000_000, config.try_to_vec().unwrap())
+    }
     
-    // Create vote record
-    let clock = solana_program::clock::Clock::get()?;
-    let current_time = clock.unix_timestamp as u64;
+    /// A funded project with one validated milestone, in an account sized
+    /// exactly to its data as `InitializeProject` leaves it
+    fn validated_project(program_id: &Pubkey, disputed: bool) -> (TestAccount, Project) {
+        let mut milestone = Milestone::from(MilestoneArgs {
+            name: "Prototype".to_string(),
+            description: String::new(),
+            amount: 1_000,
+            dependencies: Vec::new(),
+            due_after: 1_000,
+        });
+        milestone.status = MilestoneStatus::Validated;
+        milestone.validations = 1;
+        milestone.validated_at = 1;
+        milestone.funder_approve_weight = 1_000;
+        milestone.disputed = disputed;
+        
+        let project = Project {
+            account_type: AccountType::Project,
+            version: Project::VERSION,
+            creator: Pubkey::new_unique(),
+            title: "Project".to_string(),
+            description: String::new(),
+            goal_amount: 1_000,
+            ledger: EscrowLedger { total_deposited: 1_000, ..EscrowLedger::default() },
+            start_time: 0,
+            end_time: 1,
+            milestones: vec![milestone],
+            status: ProjectStatus::Funded,
+            approve_votes: 1,
+            reject_votes: 0,
+            vault_bump: 0,
+            funding_mode: FundingMode::AllOrNothing,
+            mint: None,
+            vault_token_bump: 0,
+            voting_start: 0,
+            voting_end: 0,
+            remainder_policy: RemainderPolicy::Exact,
+            sequential_milestones: false,
+            activated_at: 1,
+            bond_bump: 0,
+            creator_bond: 0,
+            bond_paid: 0,
+        };
+        let account = TestAccount::new(
+            Pubkey::new_unique(),
+            *program_id,
+            1_000_000_000,
+            project.try_to_vec().unwrap(),
+        );
+        (account, project)
+    }
     
-    let vote_data = Vote {
-        voter: *voter_account.key,
-        project: *project_account.key,
-        approve,
-        timestamp: current_time,
-    };
+    #[test]
+    fn project_at_every_limit_serializes_to_max_len() {
+        let (_, mut project) = validated_project(&Pubkey::new_unique(), false);
+        project.title = "t".repeat(MAX_TITLE_LEN);
+        project.description = "d".repeat(MAX_DESCRIPTION_LEN);
+        project.mint = Some(Pubkey::new_unique());
+        project.milestones = (0..MAX_MILESTONES)
+            .map(|_| {
+                let mut milestone = Milestone::from(MilestoneArgs {
+                    name: "n".repeat(MAX_MILESTONE_NAME_LEN),
+                    description: "d".repeat(MAX_MILESTONE_DESCRIPTION_LEN),
+                    amount: 1,
+                    dependencies: vec![0; MAX_MILESTONES - 1],
+                    due_after: 1,
+                });
+                milestone.evidence = (0..MAX_MILESTONE_EVIDENCE)
+                    .map(|_| MilestoneEvidence { hash: [0; 32], uri: "u".repeat(MAX_EVIDENCE_URI_LEN) })
+                    .collect();
+                milestone.overturned_rounds = vec![0; RESERVED_OVERTURNED_ROUNDS];
+                milestone
+            })
+            .collect();
+        
+        assert_eq!(project.try_to_vec().unwrap().len(), Project::MAX_LEN);
+    }
     
-    // Serialize and save vote data
-    vote_data.serialize(&mut *vote_account.data.borrow_mut())?;
+    fn assert_overturned(project_data: &[u8], original_len: usize) {
+        let project = Project::deserialize(&mut &project_data[..]).unwrap();
+        let milestone = &project.milestones[0];
+        assert!(project_data.len() > original_len);
+        assert_eq!(milestone.status, MilestoneStatus::Pending);
+        assert_eq!(milestone.round, 1);
+        assert_eq!(milestone.funder_approve_weight, 0);
+        assert_eq!(milestone.overturned_rounds, vec![0]);
+    }
     
-    msg!("Vote recorded: {}", if approve { "approve" } else { "reject" });
-    Ok(())
+    #[test]
+    fn overturn_grows_exactly_sized_project_account() {
+        let program_id = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let (project, _) = validated_project(&program_id, false);
+        let original_len = project.data.len();
+        let accounts = [
+            TestAccount::signer(admin),
+            config_account(&program_id, admin),
+            project,
+            TestAccount::new(system_program::ID, Pubkey::default(), 1, Vec::new()),
+        ];
+        
+        let (result, data) = run(
+            &program_id,
+            &accounts,
+            ColabioInstruction::OverturnMilestone { milestone_index: 0 },
+        );
+        
+        assert_eq!(result, Ok(()));
+        assert_overturned(&data[2], original_len);
+    }
+    
+    #[test]
+    fn last_reserved_overturn_cancels_project() {
+        let program_id = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let (mut project, mut project_data) = validated_project(&program_id, false);
+        let milestone = &mut project_data.milestones[0];
+        milestone.round = RESERVED_OVERTURNED_ROUNDS as u32 - 1;
+        milestone.overturned_rounds = (0..milestone.round).collect();
+        project.data = project_data.try_to_vec().unwrap();
+        let accounts = [
+            TestAccount::signer(admin),
+            config_account(&program_id, admin),
+            project,
+            TestAccount::new(system_program::ID, Pubkey::default(), 1, Vec::new()),
+        ];
+        
+        let (result, data) = run(
+            &program_id,
+            &accounts,
+            ColabioInstruction::OverturnMilestone { milestone_index: 0 },
+        );
+        
+        let project = Project::deserialize(&mut &data[2][..]).unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(project.status, ProjectStatus::Cancelled);
+        assert_eq!(project.milestones[0].overturned_rounds.len(), RESERVED_OVERTURNED_ROUNDS);
+        assert!(project.is_refundable());
+    }
+    
+    #[test]
+    fn upheld_dispute_grows_exactly_sized_project_account() {
+        let program_id = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let challenger = Pubkey::new_unique();
+        let config = config_account(&program_id, admin);
+        let fee_recipient = Config::deserialize(&mut &config.data[..]).unwrap().params.fee_recipient;
+        let (project, _) = validated_project(&program_id, true);
+        let original_len = project.data.len();
+        let (dispute_key, dispute_bump) = find_dispute_address(&program_id, &project.key, 0, 0);
+        let dispute = Dispute {
+            account_type: AccountType::Dispute,
+            version: Dispute::VERSION,
+            project: project.key,
+            milestone_index: 0,
+            round: 0,
+            challenger,
+            evidence_hash: [0; 32],
+            bond: 1_000,
+            opened_at: 1,
+            voting_end: 2,
+            uphold_weight: 0,
+            dismiss_weight: 0,
+            status: DisputeStatus::Open,
+            bump: dispute_bump,
+        };
+        let accounts = [
+            project,
+            config,
+            TestAccount::new(dispute_key, program_id, 1_000_000, dispute.try_to_vec().unwrap()),
+            TestAccount::new(challenger, system_program::ID, 0, Vec::new()),
+            TestAccount::new(fee_recipient, system_program::ID, 0, Vec::new()),
+            TestAccount::signer(admin),
+            TestAccount::new(system_program::ID, Pubkey::default(), 1, Vec::new()),
+        ];
+        
+        let (result, data) = run(&program_id, &accounts, ColabioInstruction::ArbitrateDispute { uphold: true });
+        
+        assert_eq!(result, Ok(()));
+        assert_overturned(&data[0], original_len);
+    }
+    
+    /// Runs `MarkDefault` on an overdue project in the given state
+    fn mark_default(status: ProjectStatus, total_deposited: u64) -> (ProgramResult, Project) {
+        let program_id = Pubkey::new_unique();
+        let (mut project, mut project_data) = validated_project(&program_id, false);
+        project_data.status = status;
+        project_data.ledger.total_deposited = total_deposited;
+        project.data = project_data.try_to_vec().unwrap();
+        let accounts = [project, config_account(&program_id, Pubkey::new_unique())];
+        
+        let (result, data) = run(&program_id, &accounts, ColabioInstruction::MarkDefault { milestone_index: 0 });
+        (result, Project::deserialize(&mut &data[0][..]).unwrap())
+    }
+    
+    #[test]
+    fn mark_default_requires_underfunded_all_or_nothing_project_to_finalize() {
+        let (result, project) = mark_default(ProjectStatus::Active, 500);
+        
+        assert_eq!(result, Err(ColabioError::FundingNotFinalized.into()));
+        assert_eq!(project.status, ProjectStatus::Active);
+        assert!(!project.bond_forfeited());
+    }
+    
+    #[test]
+    fn mark_default_defaults_overdue_funded_project() {
+        let (result, project) = mark_default(ProjectStatus::Funded, 1_000);
+        
+        assert_eq!(result, Ok(()));
+        assert_eq!(project.status, ProjectStatus::Defaulted);
+    }
+    
+    #[test]
+    fn config_requires_unbonding_to_outlast_challenge_and_dispute() {
+        assert_eq!(config_params().validate(), Ok(()));
+        
+        let short = ConfigParams { unbonding_period: 1_000, ..config_params() };
+        assert_eq!(short.validate(), Err(ColabioError::InvalidConfigParams.into()));
+        
+        let instant = ConfigParams { unbonding_period: 0, challenge_period: 0, ..config_params() };
+        assert_eq!(instant.validate(), Err(ColabioError::InvalidConfigParams.into()));
+    }
+    
+    /// Submits one evidence entry for a pending milestone with the given
+    /// number of validations this round
+    fn submit_evidence(validations: u32) -> (ProgramResult, Project) {
+        let program_id = Pubkey::new_unique();
+        let (mut project, mut project_data) = validated_project(&program_id, false);
+        project_data.milestones[0].status = MilestoneStatus::Pending;
+        project_data.milestones[0].validations = validations;
+        project.data = project_data.try_to_vec().unwrap();
+        let accounts = [
+            TestAccount::signer(project_data.creator),
+            project,
+            config_account(&program_id, Pubkey::new_unique()),
+            TestAccount::new(system_program::ID, Pubkey::default(), 1, Vec::new()),
+        ];
+        let evidence = vec![MilestoneEvidence { hash: [1; 32], uri: "ipfs://evidence".to_string() }];
+        
+        let (result, data) = run(
+            &program_id,
+            &accounts,
+            ColabioInstruction::SubmitMilestoneEvidence { milestone_index: 0, evidence },
+        );
+        (result, Project::deserialize(&mut &data[1][..]).unwrap())
+    }
+    
+    #[test]
+    fn evidence_is_appended_before_the_first_review() {
+        let (result, project) = submit_evidence(0);
+        
+        assert_eq!(result, Ok(()));
+        assert_eq!(project.milestones[0].evidence.len(), 1);
+    }
+    
+    #[test]
+    fn evidence_is_locked_once_the_round_has_a_validation() {
+        let (result, project) = submit_evidence(1);
+        
+        assert_eq!(result, Err(ColabioError::EvidenceLocked.into()));
+        assert!(project.milestones[0].evidence.is_empty());
+    }
+    
+    fn project_args(remainder_policy: RemainderPolicy, goal_amount: u64) -> ProjectArgs {
+        ProjectArgs {
+            title: "Project".to_string(),
+            description: String::new(),
+            goal_amount,
+            duration: 1_000,
+            milestones: [400, 600]
+                .iter()
+                .map(|amount| MilestoneArgs {
+                    name: "Milestone".to_string(),
+                    description: String::new(),
+                    amount: *amount,
+                    dependencies: Vec::new(),
+                    due_after: 1_000,
+                })
+                .collect(),
+            funding_mode: FundingMode::AllOrNothing,
+            mint: None,
+            remainder_policy,
+            sequential_milestones: false,
+        }
+    }
+    
+    #[test]
+    fn refund_shares_never_exceed_unreleased() {
+        let contributions = [1, 333, 333, 334, 999_999_999, 7];
+        let total_deposited: u64 = contributions.iter().sum();
+        for total_released in [0, 1, total_deposited / 3, total_deposited - 1, total_deposited] {
+            let mut ledger = EscrowLedger { total_deposited, total_released, ..EscrowLedger::default() };
+            let unreleased = ledger.unreleased().unwrap();
+            for amount in contributions {
+                ledger.refund(ledger.pro_rata_share(amount, unreleased)).unwrap();
+            }
+            assert!(ledger.total_refunded <= unreleased);
+            assert!(ledger.held().is_ok());
+        }
+        
+        assert_eq!(EscrowLedger::default().pro_rata_share(1_000, 1_000), 0);
+    }
+    
+    #[test]
+    fn invariants_reject_over_release() {
+        let violated = Err(ColabioError::LedgerInvariantViolated.into());
+        let (_, project) = validated_project(&Pubkey::new_unique(), false);
+        assert_eq!(project.check_invariants(), Ok(()));
+        
+        // A milestone paid more than its amount, even with escrow to cover it
+        let (_, mut project) = validated_project(&Pubkey::new_unique(), false);
+        project.ledger.total_deposited = 2_000;
+        project.milestones[0].released_amount = 1_001;
+        project.ledger.release(1_001, 0).unwrap();
+        assert_eq!(project.check_invariants(), violated);
+        
+        // A payout no milestone accounts for
+        let (_, mut project) = validated_project(&Pubkey::new_unique(), false);
+        project.ledger.release(500, 0).unwrap();
+        assert_eq!(project.check_invariants(), violated);
+        
+        // Refunds beyond what the milestones left in escrow
+        let (_, mut project) = validated_project(&Pubkey::new_unique(), false);
+        project.milestones[0].released_amount = 600;
+        project.ledger.release(590, 10).unwrap();
+        project.ledger.refund(401).unwrap();
+        assert_eq!(project.check_invariants(), violated);
+        
+        // More bond paid out than was deposited
+        let (_, mut project) = validated_project(&Pubkey::new_unique(), false);
+        project.creator_bond = 100;
+        project.bond_paid = 101;
+        assert_eq!(project.check_invariants(), violated);
+    }
+    
+    #[test]
+    fn exact_budget_must_match_goal() {
+        let mismatch = Err(ColabioError::MilestoneBudgetMismatch.into());
+        let params = config_params();
+        
+        assert_eq!(project_args(RemainderPolicy::Exact, 1_000).validate(&params), Ok(()));
+        assert_eq!(project_args(RemainderPolicy::Exact, 999).validate(&params), mismatch);
+        assert_eq!(project_args(RemainderPolicy::Exact, 1_001).validate(&params), mismatch);
+    }
+    
+    #[test]
+    fn refund_on_completion_budget_may_fall_short_of_goal() {
+        let params = config_params();
+        
+        assert_eq!(project_args(RemainderPolicy::RefundOnCompletion, 1_000).validate(&params), Ok(()));
+        assert_eq!(project_args(RemainderPolicy::RefundOnCompletion, 1_500).validate(&params), Ok(()));
+        assert_eq!(
+            project_args(RemainderPolicy::RefundOnCompletion, 999).validate(&params),
+            Err(ColabioError::MilestoneBudgetMismatch.into())
+        );
+    }
+    
+    #[test]
+    fn arithmetic_overflow_is_an_error() {
+        let overflow: ProgramError = ColabioError::ArithmeticOverflow.into();
+        let mut ledger = EscrowLedger {
+            total_deposited: u64::MAX,
+            total_released: u64::MAX,
+            total_refunded: u64::MAX,
+            total_fees: 1,
+        };
+        assert_eq!(ledger.deposit(1), Err(overflow.clone()));
+        assert_eq!(ledger.release(1, 0), Err(overflow.clone()));
+        assert_eq!(ledger.refund(1), Err(overflow.clone()));
+        assert_eq!(ledger.paid_out(), Err(overflow.clone()));
+        
+        // Paying out more than was deposited underflows rather than wrapping
+        let ledger = EscrowLedger { total_deposited: 1, total_released: 2, ..EscrowLedger::default() };
+        assert_eq!(ledger.unreleased(), Err(overflow.clone()));
+        
+        // Milestone amounts that overflow the budget are a budget mismatch
+        let mut args = project_args(RemainderPolicy::RefundOnCompletion, u64::MAX);
+        args.milestones[0].amount = u64::MAX;
+        assert_eq!(args.validate(&config_params()), Err(ColabioError::MilestoneBudgetMismatch.into()));
+        
+        let (_, mut project) = validated_project(&Pubkey::new_unique(), false);
+        project.end_time = u64::MAX;
+        assert_eq!(project.milestone_due_date(0), Err(overflow));
+    }
+    
+    #[test]
+    fn funder_vote_needs_quorum_and_ratio() {
+        let params = ConfigParams {
+            funder_quorum_bps: 5_000,
+            funder_approval_ratio_bps: 6_000,
+            ..config_params()
+        };
+        let (_, mut project) = validated_project(&Pubkey::new_unique(), false);
+        let milestone = &mut project.milestones[0];
+        
+        for (approve, reject, passed) in [
+            (0, 0, false),
+            (400, 0, false),
+            (600, 0, true),
+            (500, 400, false),
+            (600, 400, true),
+        ] {
+            milestone.funder_approve_weight = approve;
+            milestone.funder_reject_weight = reject;
+            assert_eq!(milestone.funder_vote_passed(&params, 1_000), passed, "{} / {}", approve, reject);
+        }
+    }
+    
+    #[test]
+    fn bond_goes_to_contributors_only_when_the_creator_fails_them() {
+        let (_, mut project) = validated_project(&Pubkey::new_unique(), false);
+        project.creator_bond = 100;
+        
+        for (status, activated_at, total_deposited, forfeited, reclaimable) in [
+            (ProjectStatus::Defaulted, 1, 1_000, true, false),
+            (ProjectStatus::Cancelled, 1, 1_000, true, false),
+            (ProjectStatus::Cancelled, 0, 1_000, false, true),
+            (ProjectStatus::Defaulted, 1, 0, false, true),
+            (ProjectStatus::Completed, 1, 1_000, false, true),
+            (ProjectStatus::Failed, 1, 1_000, false, true),
+            (ProjectStatus::Funded, 1, 1_000, false, false),
+        ] {
+            project.status = status;
+            project.activated_at = activated_at;
+            project.ledger.total_deposited = total_deposited;
+            assert_eq!(project.bond_forfeited(), forfeited, "{:?}", project.status);
+            assert_eq!(project.bond_reclaimable(), reclaimable, "{:?}", project.status);
+        }
+        
+        // A bond can only be taken back once
+        project.status = ProjectStatus::Completed;
+        project.bond_paid = 100;
+        assert!(!project.bond_reclaimable());
+    }
+    
+    #[test]
+    fn unpack_rejects_empty_and_short_data() {
+        assert_eq!(ColabioInstruction::unpack(&[]).unwrap_err(), ProgramError::InvalidInstructionData);
+        assert_eq!(
+            ColabioInstruction::unpack(&[INSTRUCTION_VERSION]).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+        
+        // Every prefix of a valid instruction is too short to decode
+        let data = ColabioInstruction::ValidateMilestone {
+            milestone_index: 1,
+            evidence_hash: [7; 32],
+            approve: true,
+        }
+        .pack();
+        for len in 1..data.len() {
+            assert_eq!(
+                ColabioInstruction::unpack(&data[..len]).unwrap_err(),
+                ProgramError::InvalidInstructionData
+            );
+        }
+    }
+    
+    #[test]
+    fn unpack_rejects_legacy_tag_first_data() {
+        for tag in 0..=6 {
+            assert_eq!(
+                ColabioInstruction::unpack(&[tag, 1, 0, 0, 0, 0, 0, 0, 0]).unwrap_err(),
+                ColabioError::UnsupportedInstructionVersion.into()
+            );
+        }
+    }
+    
+    #[test]
+    fn pack_and_unpack_round_trip() {
+        let instructions = [
+            ColabioInstruction::InitializeProject { args: project_args(RemainderPolicy::Exact, 1_000) },
+            ColabioInstruction::Contribute { amount: 42, mint_receipt: true },
+            ColabioInstruction::ValidateMilestone { milestone_index: 3, evidence_hash: [9; 32], approve: false },
+            ColabioInstruction::SubmitMilestoneEvidence {
+                milestone_index: 0,
+                evidence: vec![MilestoneEvidence { hash: [1; 32], uri: "ipfs://evidence".to_string() }],
+            },
+            ColabioInstruction::ReclaimBond {},
+        ];
+        for instruction in instructions {
+            let data = instruction.pack();
+            assert_eq!(data[0], INSTRUCTION_VERSION);
+            let unpacked = ColabioInstruction::unpack(&data).unwrap();
+            assert_eq!(unpacked.pack(), data);
+            assert_eq!(format!("{:?}", unpacked), format!("{:?}", instruction));
+        }
+    }
 }